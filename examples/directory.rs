@@ -0,0 +1,104 @@
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{error::Error, io};
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders},
+    Terminal,
+};
+
+use tui_tree_widget::{DirectoryEntry, DirectoryTree, Tree, TreeState};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Terminal initialization
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // App
+    let directory = DirectoryTree::new(".").ignore("target");
+    let res = run_app(&mut terminal, directory);
+
+    // restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    match res {
+        Ok(Some(path)) => println!("{}", path),
+        Ok(None) => {}
+        Err(err) => println!("{:?}", err),
+    }
+
+    Ok(())
+}
+
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut directory: DirectoryTree,
+) -> io::Result<Option<String>> {
+    let mut state = TreeState::default();
+    loop {
+        directory.load_opened(&state);
+        terminal.draw(|f| {
+            let area = f.size();
+
+            let title = format!(
+                "{} (. toggles hidden files, Enter picks)",
+                directory.root().display()
+            );
+            let items = Tree::new(directory.items())
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .highlight_style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::LightGreen)
+                        .add_modifier(Modifier::BOLD),
+                );
+            f.render_stateful_widget(items, area, &mut state);
+        })?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Char('q') => return Ok(None),
+                KeyCode::Char('.') => {
+                    directory.set_show_hidden(!directory.shows_hidden(), &mut state);
+                }
+                KeyCode::Enter => {
+                    let path = directory.get(&state.selected()).map(DirectoryEntry::path);
+                    return Ok(path.map(|path| path.display().to_string()));
+                }
+                KeyCode::Char(' ') => state.toggle_selected(),
+                KeyCode::Left => {
+                    state.key_left(&directory.items());
+                }
+                KeyCode::Right => {
+                    state.key_right(&directory.items());
+                }
+                KeyCode::Down => {
+                    state.key_down(&directory.items());
+                }
+                KeyCode::Up => {
+                    state.key_up(&directory.items());
+                }
+                KeyCode::Home => {
+                    state.select_first(&directory.items());
+                }
+                KeyCode::End => {
+                    state.select_last(&directory.items());
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ mod util;
 
 use crate::util::StatefulTree;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -14,10 +14,12 @@ use tui::{
     Terminal,
 };
 
-use tui_tree_widget::{Tree, TreeItem};
+use tui_tree_widget::{Keymap, NavigationOutcome, Tree, TreeAction, TreeItem};
 
 struct App<'a> {
     tree: StatefulTree<'a>,
+    keymap: Keymap,
+    last_action: Option<(TreeAction, NavigationOutcome)>,
 }
 
 impl<'a> App<'a> {
@@ -35,6 +37,8 @@ impl<'a> App<'a> {
                 ),
                 TreeItem::new_leaf("h"),
             ]),
+            keymap: Keymap::default(),
+            last_action: None,
         }
     }
 }
@@ -72,12 +76,13 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
         terminal.draw(|f| {
             let area = f.size();
 
+            let title = format!(
+                "Tree Widget {:?} {:?}",
+                app.tree.state.selected(),
+                app.last_action
+            );
             let items = Tree::new(app.tree.items.clone())
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title(format!("Tree Widget {:?}", app.tree.state)),
-                )
+                .block(Block::default().borders(Borders::ALL).title(title))
                 .highlight_style(
                     Style::default()
                         .fg(Color::Black)
@@ -88,17 +93,18 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
             f.render_stateful_widget(items, area, &mut app.tree.state);
         })?;
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                KeyCode::Char('q') => return Ok(()),
-                KeyCode::Char('\n' | ' ') => app.tree.toggle(),
-                KeyCode::Left => app.tree.left(),
-                KeyCode::Right => app.tree.right(),
-                KeyCode::Down => app.tree.down(),
-                KeyCode::Up => app.tree.up(),
-                KeyCode::Home => app.tree.first(),
-                KeyCode::End => app.tree.last(),
-                _ => {}
+        match event::read()? {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                ..
+            }) => return Ok(()),
+            event => {
+                let handled =
+                    app.keymap
+                        .handle_crossterm(&mut app.tree.state, &app.tree.items, &event);
+                if handled.is_some() {
+                    app.last_action = handled;
+                }
             }
         }
     }
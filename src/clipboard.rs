@@ -0,0 +1,353 @@
+use crate::identifier::{get_without_leaf, TreeIdentifier, TreeIdentifierVec};
+use crate::{get_children_mut, get_item, TreeItem, TreeState};
+
+/// Where to [`paste`](Clipboard::paste) a subtree relative to the target node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PastePosition {
+    /// Insert as the sibling right in front of the target
+    Before,
+    /// Insert as the sibling right after the target
+    After,
+    /// Insert as the last child of the target
+    Inside,
+}
+
+/// A subtree detached or cloned from the items of a [`Tree`](crate::Tree)
+///
+/// The opened nodes of the subtree are carried along and restored on [`paste`](Self::paste).
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{Clipboard, PastePosition, TreeItem, TreeState};
+/// let mut items = vec![
+///     TreeItem::new("a", vec![TreeItem::new_leaf("b")]),
+///     TreeItem::new_leaf("c"),
+/// ];
+/// let mut state = TreeState::default();
+///
+/// let clipboard = Clipboard::cut(&mut items, &mut state, &[0, 0]).unwrap();
+/// let pasted = clipboard.paste(&mut items, &mut state, &[1], PastePosition::Inside);
+/// assert_eq!(pasted, Some(vec![1, 0]));
+/// assert!(items[0].children().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Clipboard<'a> {
+    item: TreeItem<'a>,
+    /// Opened nodes relative to `item` which itself is `[]`
+    opened: Vec<TreeIdentifierVec>,
+}
+
+impl<'a> Clipboard<'a> {
+    /// Clone the subtree at `identifier`.
+    /// Returns `None` when the `identifier` does not reference an item.
+    #[must_use]
+    pub fn copy(
+        items: &[TreeItem<'a>],
+        state: &TreeState,
+        identifier: TreeIdentifier,
+    ) -> Option<Self> {
+        let item = get_item(items, identifier)?.clone();
+        let opened = relative_opened(state, identifier);
+        Some(Self { item, opened })
+    }
+
+    /// Detach the subtree at `identifier` from the `items`.
+    /// Returns `None` when the `identifier` does not reference an item.
+    ///
+    /// The `state` is updated so it keeps pointing to the same nodes which moved due to the removal.
+    /// When the selection was inside the removed subtree it moves to the node taking its place,
+    /// the previous sibling or the parent.
+    /// Marks and jumps inside the removed subtree are removed.
+    pub fn cut(
+        items: &mut Vec<TreeItem<'a>>,
+        state: &mut TreeState,
+        identifier: TreeIdentifier,
+    ) -> Option<Self> {
+        let (parent, index) = get_without_leaf(identifier);
+        let index = index?;
+        let siblings = get_children_mut(items, parent)?;
+        if index >= siblings.len() {
+            return None;
+        }
+        let item = siblings.remove(index);
+        let remaining = siblings.len();
+
+        let opened = relative_opened(state, identifier);
+        state.opened = state
+            .opened
+            .drain()
+            .filter_map(|o| shift_after_removal(&o, identifier))
+            .collect();
+        state.remap_identifiers(|o| shift_after_removal(o, identifier));
+
+        if state.selected.starts_with(identifier) {
+            let mut selected = parent.to_vec();
+            if index < remaining {
+                selected.push(index);
+            } else if index > 0 {
+                selected.push(index - 1);
+            }
+            state.selected = selected;
+        } else if let Some(selected) = shift_after_removal(&state.selected, identifier) {
+            state.selected = selected;
+        }
+
+        Some(Self { item, opened })
+    }
+
+    /// The root of the subtree
+    #[must_use]
+    pub const fn item(&self) -> &TreeItem<'a> {
+        &self.item
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn into_item(self) -> TreeItem<'a> {
+        self.item
+    }
+
+    /// Insert a clone of the subtree relative to the `target` node.
+    /// Returns the identifier of the pasted node or `None` when the `target` does not exist.
+    ///
+    /// An empty `target` references the top level so only [`PastePosition::Inside`] is possible there.
+    /// Pasting inside a node opens it in order to make the pasted node visible.
+    /// The selection is kept on the same node and not moved to the pasted one.
+    pub fn paste(
+        &self,
+        items: &mut Vec<TreeItem<'a>>,
+        state: &mut TreeState,
+        target: TreeIdentifier,
+        position: PastePosition,
+    ) -> Option<TreeIdentifierVec> {
+        let (parent, index) = match position {
+            PastePosition::Before | PastePosition::After => {
+                let (parent, index) = get_without_leaf(target);
+                let index = index?;
+                let siblings = get_children_mut(items, parent)?;
+                if index >= siblings.len() {
+                    return None;
+                }
+                let index = if position == PastePosition::After {
+                    index + 1
+                } else {
+                    index
+                };
+                (parent.to_vec(), index)
+            }
+            PastePosition::Inside => {
+                let index = get_children_mut(items, target)?.len();
+                (target.to_vec(), index)
+            }
+        };
+
+        let mut identifier = parent.clone();
+        identifier.push(index);
+
+        get_children_mut(items, &parent)?.insert(index, self.item.clone());
+
+        state.opened = state
+            .opened
+            .drain()
+            .map(|o| shift_for_insertion(o, &identifier))
+            .collect();
+        state.selected = shift_for_insertion(std::mem::take(&mut state.selected), &identifier);
+        state.remap_identifiers(|o| Some(shift_for_insertion(o.to_vec(), &identifier)));
+
+        for relative in &self.opened {
+            let mut opened = identifier.clone();
+            opened.extend_from_slice(relative);
+            state.opened.insert(opened);
+        }
+        if position == PastePosition::Inside {
+            state.open(parent);
+        }
+
+        Some(identifier)
+    }
+}
+
+/// Opened nodes inside the subtree at `root` relative to it
+fn relative_opened(state: &TreeState, root: TreeIdentifier) -> Vec<TreeIdentifierVec> {
+    state
+        .opened
+        .iter()
+        .filter_map(|o| o.strip_prefix(root))
+        .map(<[usize]>::to_vec)
+        .collect()
+}
+
+/// Where the node `identifier` ends up after `removed` was removed.
+/// Returns `None` when `identifier` was inside the removed subtree.
+fn shift_after_removal(
+    identifier: TreeIdentifier,
+    removed: TreeIdentifier,
+) -> Option<TreeIdentifierVec> {
+    if identifier.starts_with(removed) {
+        return None;
+    }
+    let mut result = identifier.to_vec();
+    if let (parent, Some(index)) = get_without_leaf(removed) {
+        let depth = parent.len();
+        if identifier.starts_with(parent) && identifier.get(depth).map_or(false, |i| *i > index) {
+            result[depth] -= 1;
+        }
+    }
+    Some(result)
+}
+
+/// Where the node `identifier` ends up after something was inserted at `inserted`
+fn shift_for_insertion(
+    mut identifier: TreeIdentifierVec,
+    inserted: TreeIdentifier,
+) -> TreeIdentifierVec {
+    if let (parent, Some(index)) = get_without_leaf(inserted) {
+        let depth = parent.len();
+        if identifier.starts_with(parent) && identifier.get(depth).map_or(false, |i| *i >= index) {
+            identifier[depth] += 1;
+        }
+    }
+    identifier
+}
+
+#[cfg(test)]
+fn get_visible_texts(items: &[TreeItem], state: &TreeState) -> Vec<String> {
+    crate::flatten(&state.get_all_opened(), items)
+        .iter()
+        .map(|o| crate::flatten::get_naive_string_from_text(&o.item.text))
+        .collect()
+}
+
+#[test]
+fn copy_keeps_items() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    let clipboard = Clipboard::copy(&items, &state, &[1]).unwrap();
+    assert_eq!(clipboard.item().children().len(), 3);
+    let mut opened = clipboard.opened;
+    opened.sort();
+    assert_eq!(opened, [vec![], vec![1]]);
+    assert_eq!(get_visible_texts(&items, &state).len(), 8);
+}
+
+#[test]
+fn copy_missing_is_none() {
+    let items = crate::flatten::get_example_tree_items();
+    let state = TreeState::default();
+    assert!(Clipboard::copy(&items, &state, &[5]).is_none());
+    assert!(Clipboard::copy(&items, &state, &[]).is_none());
+}
+
+#[test]
+fn cut_shifts_state() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 2]);
+    let clipboard = Clipboard::cut(&mut items, &mut state, &[1, 0]).unwrap();
+    assert_eq!(
+        crate::flatten::get_naive_string_from_text(&clipboard.item().text),
+        "c"
+    );
+    assert_eq!(state.selected(), [1, 1]);
+    assert_eq!(
+        get_visible_texts(&items, &state),
+        ["a", "b", "d", "e", "f", "g", "h"]
+    );
+}
+
+#[test]
+fn cut_selected_moves_selection() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![1, 2]);
+    state.start_editing(&items);
+    Clipboard::cut(&mut items, &mut state, &[1, 1]).unwrap();
+    assert_eq!(state.selected(), [1, 1]);
+    assert_eq!(state.editing_identifier(), Some(&[1, 1][..]));
+    Clipboard::cut(&mut items, &mut state, &[1]).unwrap();
+    assert_eq!(state.selected(), [1]);
+    assert!(state.editing().is_none());
+    assert_eq!(get_visible_texts(&items, &state), ["a", "h"]);
+    assert!(state.get_all_opened().is_empty());
+}
+
+#[test]
+fn paste_carries_opened() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 0]);
+    state.set_mark('e');
+    state.select(vec![2]);
+    state.set_mark('h');
+    let clipboard = Clipboard::cut(&mut items, &mut state, &[1, 1]).unwrap();
+    assert_eq!(get_visible_texts(&items, &state), ["a", "b", "c", "g", "h"]);
+    assert_eq!(state.marks(), [('h', vec![2])]);
+
+    let pasted = clipboard.paste(&mut items, &mut state, &[0], PastePosition::Before);
+    assert_eq!(pasted, Some(vec![0]));
+    assert_eq!(state.selected(), [3]);
+    assert_eq!(state.mark('h'), Some(&[3][..]));
+    assert_eq!(
+        get_visible_texts(&items, &state),
+        ["d", "e", "f", "a", "b", "c", "g", "h"]
+    );
+}
+
+#[test]
+fn cut_and_paste_shift_jumps() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![1, 2]);
+    state.select_last(&items);
+    Clipboard::cut(&mut items, &mut state, &[1, 1]).unwrap();
+    let clipboard = Clipboard::copy(&items, &state, &[0]).unwrap();
+    clipboard.paste(&mut items, &mut state, &[0], PastePosition::Before);
+    assert_eq!(state.jump_back(), crate::NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [2, 1]);
+    assert_eq!(
+        get_visible_texts(&items, &state),
+        ["a", "a", "b", "c", "g", "h"]
+    );
+
+    state.select_first(&items);
+    Clipboard::cut(&mut items, &mut state, &[2]).unwrap();
+    assert_eq!(state.jump_back(), crate::NavigationOutcome::AtBoundary);
+}
+
+#[test]
+fn paste_inside_opens_target() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let clipboard = Clipboard::copy(&items, &state, &[2]).unwrap();
+    let pasted = clipboard.paste(&mut items, &mut state, &[0], PastePosition::Inside);
+    assert_eq!(pasted, Some(vec![0, 0]));
+    let pasted = clipboard.paste(&mut items, &mut state, &[0, 0], PastePosition::After);
+    assert_eq!(pasted, Some(vec![0, 1]));
+    assert_eq!(get_visible_texts(&items, &state), ["a", "h", "h", "b", "h"]);
+}
+
+#[test]
+fn paste_invalid_target_is_none() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let clipboard = Clipboard::copy(&items, &state, &[0]).unwrap();
+    assert!(clipboard
+        .paste(&mut items, &mut state, &[], PastePosition::After)
+        .is_none());
+    assert!(clipboard
+        .paste(&mut items, &mut state, &[7], PastePosition::Before)
+        .is_none());
+    assert_eq!(
+        clipboard.paste(&mut items, &mut state, &[], PastePosition::Inside),
+        Some(vec![3])
+    );
+}
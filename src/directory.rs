@@ -0,0 +1,539 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{TreeItem, TreeState};
+
+/// What a [`DirectoryEntry`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Directory,
+    /// Symbolic links are not followed, so they never have children
+    Symlink,
+    /// Reading the parent directory failed. The name of the entry is the error message.
+    Error,
+}
+
+/// One entry of a [`DirectoryTree`]
+#[derive(Debug, Clone)]
+pub struct DirectoryEntry {
+    name: String,
+    path: PathBuf,
+    kind: EntryKind,
+    /// `None` until the directory is opened and read
+    children: Option<Vec<DirectoryEntry>>,
+}
+
+impl DirectoryEntry {
+    fn error(path: &Path, error: &std::io::Error) -> Self {
+        Self {
+            name: error.to_string(),
+            path: path.to_path_buf(),
+            kind: EntryKind::Error,
+            children: None,
+        }
+    }
+
+    /// The file name or the error message for [`EntryKind::Error`]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Path of the entry.
+    /// For [`EntryKind::Error`] this is the directory which could not be read.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub const fn kind(&self) -> EntryKind {
+        self.kind
+    }
+}
+
+/// Tree of a directory on the filesystem which reads directories lazily once they are opened
+///
+/// Call [`load_opened`](Self::load_opened) after changing the [`TreeState`] and before rendering
+/// the [`items`](Self::items) in order to read the directories which were opened in the meantime.
+/// Nothing is read before the first call, not even the `root`, so configuring it does no IO.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{DirectoryTree, Tree, TreeState};
+/// let mut state = TreeState::default();
+/// let mut directory = DirectoryTree::new(".").show_hidden(true).ignore("target");
+/// directory.load_opened(&state);
+///
+/// state.key_down(&directory.items());
+/// state.key_right(&directory.items());
+/// directory.load_opened(&state);
+///
+/// let tree_widget = Tree::new(directory.items());
+/// let path = directory.get(&state.selected()).map(|entry| entry.path());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirectoryTree<'a> {
+    root: PathBuf,
+    /// `None` until the `root` is read by the first [`load_opened`](Self::load_opened)
+    entries: Option<Vec<DirectoryEntry>>,
+    show_hidden: bool,
+    ignore: Vec<String>,
+
+    file_symbol: &'a str,
+    directory_symbol: &'a str,
+    symlink_symbol: &'a str,
+    error_symbol: &'a str,
+    /// Style used for entries which could not be read
+    error_style: Style,
+}
+
+impl<'a> DirectoryTree<'a> {
+    /// Create a tree of the directory `root`.
+    /// Its entries are read by the first [`load_opened`](Self::load_opened).
+    #[must_use]
+    pub fn new<P>(root: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            root: root.into(),
+            entries: None,
+            show_hidden: false,
+            ignore: Vec::new(),
+            file_symbol: "- ",
+            directory_symbol: "+ ",
+            symlink_symbol: "@ ",
+            error_symbol: "! ",
+            error_style: Style::default().fg(Color::Red),
+        }
+    }
+
+    /// Show entries starting with a `.`
+    #[must_use]
+    pub fn show_hidden(mut self, show_hidden: bool) -> Self {
+        self.show_hidden = show_hidden;
+        self
+    }
+
+    /// Hide entries with a name matching the glob `pattern`.
+    /// `*` matches any amount of characters and `?` matches exactly one.
+    #[must_use]
+    pub fn ignore<S>(mut self, pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.ignore.push(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn file_symbol(mut self, symbol: &'a str) -> Self {
+        self.file_symbol = symbol;
+        self
+    }
+
+    #[must_use]
+    pub const fn directory_symbol(mut self, symbol: &'a str) -> Self {
+        self.directory_symbol = symbol;
+        self
+    }
+
+    #[must_use]
+    pub const fn symlink_symbol(mut self, symbol: &'a str) -> Self {
+        self.symlink_symbol = symbol;
+        self
+    }
+
+    #[must_use]
+    pub const fn error_symbol(mut self, symbol: &'a str) -> Self {
+        self.error_symbol = symbol;
+        self
+    }
+
+    #[must_use]
+    pub const fn error_style(mut self, style: Style) -> Self {
+        self.error_style = style;
+        self
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    #[must_use]
+    pub const fn shows_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Toggle showing hidden entries.
+    /// See [`refresh`](Self::refresh) on how the `state` is kept.
+    pub fn set_show_hidden(&mut self, show_hidden: bool, state: &mut TreeState) {
+        self.show_hidden = show_hidden;
+        self.refresh(state);
+    }
+
+    /// Top level entries which were read so far
+    fn entries(&self) -> &[DirectoryEntry] {
+        self.entries.as_deref().unwrap_or_default()
+    }
+
+    /// Get the entry referenced by the `identifier`
+    #[must_use]
+    pub fn get(&self, identifier: TreeIdentifier) -> Option<&DirectoryEntry> {
+        get_entry(self.entries(), identifier)
+    }
+
+    fn get_mut(&mut self, identifier: TreeIdentifier) -> Option<&mut DirectoryEntry> {
+        let (first, rest) = identifier.split_first()?;
+        let mut current = self.entries.as_mut()?.get_mut(*first)?;
+        for index in rest {
+            current = current.children.as_mut()?.get_mut(*index)?;
+        }
+        Some(current)
+    }
+
+    /// Find the identifier of the already read entry with the given `path`
+    #[must_use]
+    pub fn find(&self, path: &Path) -> Option<TreeIdentifierVec> {
+        let mut identifier = Vec::new();
+        let mut entries = self.entries();
+        loop {
+            let (index, entry) = entries.iter().enumerate().find(|(_, entry)| {
+                entry.kind != EntryKind::Error && path.starts_with(&entry.path)
+            })?;
+            identifier.push(index);
+            if entry.path == path {
+                return Some(identifier);
+            }
+            entries = entry.children.as_ref()?;
+        }
+    }
+
+    /// Like [`find`](Self::find) but read the directories leading to the `path` when needed
+    fn find_and_load(&mut self, path: &Path) -> Option<TreeIdentifierVec> {
+        let ancestors = path
+            .ancestors()
+            .skip(1)
+            .take_while(|ancestor| ancestor.starts_with(&self.root))
+            .collect::<Vec<_>>();
+        for ancestor in ancestors.into_iter().rev() {
+            if let Some(identifier) = self.find(ancestor) {
+                self.load(&identifier);
+            }
+        }
+        self.find(path)
+    }
+
+    /// Read the `root` and all directories which are opened in the `state` but were not read yet
+    pub fn load_opened(&mut self, state: &TreeState) {
+        if self.entries.is_none() {
+            self.entries = Some(self.read_dir(&self.root));
+        }
+        let mut opened = state.get_all_opened();
+        opened.sort_by_key(Vec::len);
+        for identifier in opened {
+            self.load(&identifier);
+        }
+    }
+
+    fn load(&mut self, identifier: TreeIdentifier) {
+        let path = match self.get(identifier) {
+            Some(entry) if entry.kind == EntryKind::Directory && entry.children.is_none() => {
+                entry.path.clone()
+            }
+            _ => return,
+        };
+        let children = self.read_dir(&path);
+        if let Some(entry) = self.get_mut(identifier) {
+            entry.children = Some(children);
+        }
+    }
+
+    /// Read all directories again.
+    ///
+    /// Entries might appear or vanish which changes the identifiers.
+    /// The opened directories, the selection, the marks, the edited entry and the jump list of the `state` are kept by their path.
+    /// When the selected entry vanished its closest still existing parent is selected.
+    pub fn refresh(&mut self, state: &mut TreeState) {
+        let mut opened_paths = state
+            .opened
+            .iter()
+            .filter_map(|identifier| self.get(identifier))
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>();
+        opened_paths.sort_by_key(|path| path.components().count());
+        let selected_path = self.get(&state.selected).map(|entry| entry.path.clone());
+
+        let entries = self.read_dir(&self.root);
+        let previous = self.entries.replace(entries);
+
+        let mut opened = HashSet::new();
+        for path in opened_paths {
+            if let Some(identifier) = self.find(&path) {
+                self.load(&identifier);
+                opened.insert(identifier);
+            }
+        }
+        state.opened = opened;
+
+        if let Some(selected_path) = selected_path {
+            state.selected = selected_path
+                .ancestors()
+                .find_map(|path| self.find(path))
+                .unwrap_or_default();
+        }
+
+        state.remap_identifiers(|identifier| {
+            let previous = get_entry(previous.as_deref().unwrap_or_default(), identifier)?;
+            self.find_and_load(&previous.path)
+        });
+    }
+
+    fn is_ignored(&self, name: &str) -> bool {
+        (!self.show_hidden && name.starts_with('.'))
+            || self
+                .ignore
+                .iter()
+                .any(|pattern| glob_matches(pattern, name))
+    }
+
+    fn read_dir(&self, path: &Path) -> Vec<DirectoryEntry> {
+        let read = match fs::read_dir(path) {
+            Ok(read) => read,
+            Err(error) => return vec![DirectoryEntry::error(path, &error)],
+        };
+
+        let mut entries = Vec::new();
+        for entry in read {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(error) => {
+                    entries.push(DirectoryEntry::error(path, &error));
+                    continue;
+                }
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if self.is_ignored(&name) {
+                continue;
+            }
+            let kind = match entry.file_type() {
+                Ok(file_type) if file_type.is_symlink() => EntryKind::Symlink,
+                Ok(file_type) if file_type.is_dir() => EntryKind::Directory,
+                Ok(_) => EntryKind::File,
+                Err(error) => {
+                    entries.push(DirectoryEntry::error(&entry.path(), &error));
+                    continue;
+                }
+            };
+            entries.push(DirectoryEntry {
+                name,
+                path: entry.path(),
+                kind,
+                children: None,
+            });
+        }
+
+        entries.sort_by(|a, b| {
+            let directory_first = |entry: &DirectoryEntry| entry.kind != EntryKind::Directory;
+            directory_first(a)
+                .cmp(&directory_first(b))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        entries
+    }
+
+    /// Items to be rendered by a [`Tree`](crate::Tree)
+    ///
+    /// Directories which were not read yet contain a single placeholder child so they can be opened.
+    #[must_use]
+    pub fn items(&self) -> Vec<TreeItem<'_>> {
+        self.entries()
+            .iter()
+            .map(|entry| self.item(entry))
+            .collect()
+    }
+
+    fn item<'b>(&'b self, entry: &'b DirectoryEntry) -> TreeItem<'b> {
+        let symbol = match entry.kind {
+            EntryKind::File => self.file_symbol,
+            EntryKind::Directory => self.directory_symbol,
+            EntryKind::Symlink => self.symlink_symbol,
+            EntryKind::Error => self.error_symbol,
+        };
+        let text = Spans::from(vec![Span::raw(symbol), Span::raw(entry.name.as_str())]);
+        let children = match (&entry.children, entry.kind) {
+            (Some(children), _) => children.iter().map(|child| self.item(child)).collect(),
+            (None, EntryKind::Directory) => vec![TreeItem::new_leaf("\u{2026}")],
+            (None, _) => Vec::new(),
+        };
+        let item = TreeItem::new(text, children);
+        if entry.kind == EntryKind::Error {
+            item.style(self.error_style)
+        } else {
+            item
+        }
+    }
+}
+
+/// Check if `name` matches the `pattern` where `*` matches any amount of characters and `?` exactly one
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    let mut p = 0;
+    let mut n = 0;
+    // Position of the last `*` in the pattern and the position in the name it was tried at
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, tried)) => {
+                    p = star + 1;
+                    n = tried + 1;
+                    backtrack = Some((star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn get_entry<'a>(
+    entries: &'a [DirectoryEntry],
+    identifier: TreeIdentifier,
+) -> Option<&'a DirectoryEntry> {
+    let (first, rest) = identifier.split_first()?;
+    let mut current = entries.get(*first)?;
+    for index in rest {
+        current = current.children.as_ref()?.get(*index)?;
+    }
+    Some(current)
+}
+
+#[cfg(test)]
+fn create_test_directory(name: &str) -> PathBuf {
+    let root =
+        std::env::temp_dir().join(format!("tui-tree-widget-{}-{}", name, std::process::id()));
+    drop(fs::remove_dir_all(&root));
+    fs::create_dir_all(root.join("sub/inner")).unwrap();
+    fs::write(root.join("b.txt"), "").unwrap();
+    fs::write(root.join("a.log"), "").unwrap();
+    fs::write(root.join(".hidden"), "").unwrap();
+    fs::write(root.join("sub/c.txt"), "").unwrap();
+    root
+}
+
+#[cfg(test)]
+fn get_names(directory: &DirectoryTree, state: &TreeState) -> Vec<String> {
+    crate::flatten(&state.get_all_opened(), &directory.items())
+        .iter()
+        .map(|o| crate::get_plain_text(&o.item.text))
+        .collect()
+}
+
+#[test]
+fn glob() {
+    assert!(glob_matches("*.log", "a.log"));
+    assert!(glob_matches("a?c", "abc"));
+    assert!(glob_matches("*a*b*", "xxaxxbxx"));
+    assert!(glob_matches("*", ""));
+    assert!(!glob_matches("*.log", "a.txt"));
+    assert!(!glob_matches("a?c", "ac"));
+}
+
+#[test]
+fn reads_lazily() {
+    let root = create_test_directory("lazy");
+    let mut state = TreeState::default();
+    let mut directory = DirectoryTree::new(&root).ignore("*.log");
+    assert!(directory.items().is_empty());
+    directory.load_opened(&state);
+    assert_eq!(get_names(&directory, &state), ["+ sub", "- b.txt"]);
+    assert!(directory.get(&[0]).unwrap().children.is_none());
+
+    state.open(vec![0]);
+    assert_eq!(
+        get_names(&directory, &state),
+        ["+ sub", "\u{2026}", "- b.txt"]
+    );
+    directory.load_opened(&state);
+    assert_eq!(
+        get_names(&directory, &state),
+        ["+ sub", "+ inner", "- c.txt", "- b.txt"]
+    );
+    assert_eq!(
+        directory.get(&[0, 1]).unwrap().path(),
+        root.join("sub/c.txt")
+    );
+    assert_eq!(directory.find(&root.join("sub/c.txt")), Some(vec![0, 1]));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn refresh_keeps_state_by_path() {
+    let root = create_test_directory("refresh");
+    let mut state = TreeState::default();
+    let mut directory = DirectoryTree::new(&root);
+    state.open(vec![0]);
+    directory.load_opened(&state);
+    state.select(vec![2]);
+    state.set_mark('b');
+    state.select_first(&directory.items());
+    state.select(vec![0, 1]);
+    state.set_mark('c');
+
+    directory.set_show_hidden(true, &mut state);
+    assert_eq!(
+        get_names(&directory, &state),
+        [
+            "+ sub",
+            "+ inner",
+            "- c.txt",
+            "- .hidden",
+            "- a.log",
+            "- b.txt"
+        ]
+    );
+    assert_eq!(state.selected(), [0, 1]);
+    assert_eq!(state.marks(), [('b', vec![3]), ('c', vec![0, 1])]);
+
+    fs::remove_file(root.join("sub/c.txt")).unwrap();
+    directory.refresh(&mut state);
+    assert_eq!(state.selected(), [0]);
+    assert_eq!(state.marks(), [('b', vec![3])]);
+    state.jump_back();
+    assert_eq!(state.selected(), [3]);
+    assert_eq!(
+        directory.get(&state.selected()).unwrap().path(),
+        root.join("b.txt")
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn unreadable_is_error_row() {
+    let root = std::env::temp_dir().join("tui-tree-widget-does-not-exist");
+    let mut directory = DirectoryTree::new(&root);
+    directory.load_opened(&TreeState::default());
+    let entry = directory.get(&[0]).unwrap();
+    assert_eq!(entry.kind(), EntryKind::Error);
+    assert_eq!(directory.items()[0].children().len(), 0);
+}
@@ -0,0 +1,211 @@
+use unicode_width::UnicodeWidthStr;
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{get_item, TreeItem, TreeState};
+
+/// Renders [`TreeItem`s](TreeItem) as plain text in the style of the `tree` command
+///
+/// Top level items are not prefixed, their children are connected with guide lines.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{PlainTextExport, TreeItem, TreeState};
+/// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b"), TreeItem::new_leaf("c")])];
+/// let mut state = TreeState::default();
+/// state.open(vec![0]);
+///
+/// let text = PlainTextExport::new().render(&items, &state);
+/// assert_eq!(text, "a\n\u{251c}\u{2500}\u{2500} b\n\u{2514}\u{2500}\u{2500} c\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlainTextExport<'a> {
+    guide_lines: bool,
+    all: bool,
+    /// Symbol in front of the selected item (Shift all items to the right)
+    highlight_symbol: &'a str,
+}
+
+impl<'a> Default for PlainTextExport<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> PlainTextExport<'a> {
+    /// Create an export with guide lines which only includes the children of opened nodes
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            guide_lines: true,
+            all: false,
+            highlight_symbol: "",
+        }
+    }
+
+    /// Connect children to their parent with lines like `├── `.
+    /// Otherwise they are indented by spaces.
+    #[must_use]
+    pub const fn guide_lines(mut self, guide_lines: bool) -> Self {
+        self.guide_lines = guide_lines;
+        self
+    }
+
+    /// Include the children of all nodes instead of only the opened ones
+    #[must_use]
+    pub const fn all(mut self, all: bool) -> Self {
+        self.all = all;
+        self
+    }
+
+    #[must_use]
+    pub const fn highlight_symbol(mut self, highlight_symbol: &'a str) -> Self {
+        self.highlight_symbol = highlight_symbol;
+        self
+    }
+
+    /// Render the `items` with the opened nodes and selection of the `state`
+    #[must_use]
+    pub fn render(&self, items: &[TreeItem], state: &TreeState) -> String {
+        let mut result = String::new();
+        for (index, item) in items.iter().enumerate() {
+            self.render_item(&mut result, state, item, &[index], "", None);
+        }
+        result
+    }
+
+    /// Render the node referenced by the `identifier` and its children as if it was the only top level item.
+    /// Returns `None` when the `identifier` does not reference an item.
+    #[must_use]
+    pub fn render_subtree(
+        &self,
+        items: &[TreeItem],
+        state: &TreeState,
+        identifier: TreeIdentifier,
+    ) -> Option<String> {
+        let item = get_item(items, identifier)?;
+        let mut result = String::new();
+        self.render_item(&mut result, state, item, identifier, "", None);
+        Some(result)
+    }
+
+    /// Render one item and its visible children.
+    /// `is_last` is `None` for items at the top of the export which have no guide lines.
+    fn render_item(
+        &self,
+        result: &mut String,
+        state: &TreeState,
+        item: &TreeItem,
+        identifier: TreeIdentifier,
+        prefix: &str,
+        is_last: Option<bool>,
+    ) {
+        let (connector, continuation) = match (is_last, self.guide_lines) {
+            (None, _) => ("", ""),
+            (Some(false), true) => ("\u{251c}\u{2500}\u{2500} ", "\u{2502}   "),
+            (Some(true), true) => ("\u{2514}\u{2500}\u{2500} ", "    "),
+            (Some(_), false) => ("  ", "  "),
+        };
+
+        let has_selection = !self.highlight_symbol.is_empty() && !state.selected.is_empty();
+        let blank_symbol = " ".repeat(self.highlight_symbol.width());
+        for (line_index, line) in item.text.lines.iter().enumerate() {
+            if has_selection {
+                if state.selected == identifier && line_index == 0 {
+                    result.push_str(self.highlight_symbol);
+                } else {
+                    result.push_str(&blank_symbol);
+                }
+            }
+            result.push_str(prefix);
+            result.push_str(if line_index == 0 {
+                connector
+            } else {
+                continuation
+            });
+            for span in &line.0 {
+                result.push_str(&span.content);
+            }
+            result.push('\n');
+        }
+
+        if !self.all && !state.opened.contains(identifier) {
+            return;
+        }
+        let child_prefix = format!("{}{}", prefix, continuation);
+        let last_index = item.children.len().saturating_sub(1);
+        for (index, child) in item.children.iter().enumerate() {
+            let mut child_identifier: TreeIdentifierVec = identifier.to_vec();
+            child_identifier.push(index);
+            self.render_item(
+                result,
+                state,
+                child,
+                &child_identifier,
+                &child_prefix,
+                Some(index == last_index),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+fn get_example_state() -> TreeState {
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state
+}
+
+#[test]
+fn only_opened() {
+    let items = crate::flatten::get_example_tree_items();
+    let text = PlainTextExport::new().render(&items, &get_example_state());
+    assert_eq!(
+        text,
+        "a\nb\n\u{251c}\u{2500}\u{2500} c\n\u{251c}\u{2500}\u{2500} d\n\u{2514}\u{2500}\u{2500} g\nh\n"
+    );
+}
+
+#[test]
+fn all_without_guide_lines() {
+    let items = crate::flatten::get_example_tree_items();
+    let text = PlainTextExport::new()
+        .all(true)
+        .guide_lines(false)
+        .render(&items, &TreeState::default());
+    assert_eq!(text, "a\nb\n  c\n  d\n    e\n    f\n  g\nh\n");
+}
+
+#[test]
+fn subtree_with_highlight_symbol() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = get_example_state();
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 1]);
+    let text = PlainTextExport::new()
+        .highlight_symbol(">> ")
+        .render_subtree(&items, &state, &[1, 1])
+        .unwrap();
+    assert_eq!(
+        text,
+        "   d\n   \u{251c}\u{2500}\u{2500} e\n>> \u{2514}\u{2500}\u{2500} f\n"
+    );
+    assert!(PlainTextExport::new()
+        .render_subtree(&items, &state, &[3])
+        .is_none());
+}
+
+#[test]
+fn multiline_items() {
+    let items = vec![TreeItem::new(
+        "a",
+        vec![TreeItem::new_leaf("b\nc"), TreeItem::new_leaf("d\ne")],
+    )];
+    let text = PlainTextExport::new()
+        .all(true)
+        .render(&items, &TreeState::default());
+    assert_eq!(
+        text,
+        "a\n\u{251c}\u{2500}\u{2500} b\n\u{2502}   c\n\u{2514}\u{2500}\u{2500} d\n    e\n"
+    );
+}
@@ -46,7 +46,7 @@ fn internal<'a>(
 }
 
 #[cfg(test)]
-fn get_naive_string_from_text(text: &tui::text::Text<'_>) -> String {
+pub(crate) fn get_naive_string_from_text(text: &tui::text::Text<'_>) -> String {
     text.lines
         .first()
         .unwrap()
@@ -58,7 +58,7 @@ fn get_naive_string_from_text(text: &tui::text::Text<'_>) -> String {
 }
 
 #[cfg(test)]
-fn get_example_tree_items() -> Vec<TreeItem<'static>> {
+pub(crate) fn get_example_tree_items() -> Vec<TreeItem<'static>> {
     vec![
         TreeItem::new_leaf("a"),
         TreeItem::new(
@@ -73,6 +73,23 @@ fn get_example_tree_items() -> Vec<TreeItem<'static>> {
     ]
 }
 
+#[cfg(test)]
+pub(crate) fn get_all_texts_indented(items: &[TreeItem]) -> Vec<String> {
+    fn internal(items: &[TreeItem], depth: usize, result: &mut Vec<String>) {
+        for item in items {
+            result.push(format!(
+                "{}{}",
+                " ".repeat(depth),
+                crate::get_plain_text(&item.text)
+            ));
+            internal(item.children(), depth + 1, result);
+        }
+    }
+    let mut result = Vec::new();
+    internal(items, 0, &mut result);
+    result
+}
+
 #[test]
 fn get_opened_nothing_opened_is_top_level() {
     let items = get_example_tree_items();
@@ -16,11 +16,11 @@ pub type TreeIdentifierVec = Vec<usize>;
 /// assert_eq!(leaf, Some(6));
 ///
 /// let (branch, leaf) = get_identifier_without_leaf(&[2]);
-/// assert_eq!(branch, []);
+/// assert_eq!(branch, [] as [usize; 0]);
 /// assert_eq!(leaf, Some(2));
 ///
 /// let (branch, leaf) = get_identifier_without_leaf(&[]);
-/// assert_eq!(branch, []);
+/// assert_eq!(branch, [] as [usize; 0]);
 /// assert_eq!(leaf, None);
 /// ```
 #[must_use]
@@ -0,0 +1,714 @@
+use std::collections::HashMap;
+
+use crate::{NavigationOutcome, TreeItem, TreeState};
+
+/// A key press independent of the terminal backend
+///
+/// With the `crossterm`, `termion` (unix only) or `termwiz` feature it can be created from their events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Ctrl(char),
+    Alt(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Enter,
+    Esc,
+    Backspace,
+    Tab,
+}
+
+/// A mouse event independent of the terminal backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseInput {
+    /// The left button was pressed at the terminal position
+    Click {
+        column: u16,
+        row: u16,
+    },
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Action on a [`TreeState`] which can be bound to a [`Key`] in a [`Keymap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TreeAction {
+    /// See [`TreeState::key_up`]
+    KeyUp,
+    /// See [`TreeState::key_down`]
+    KeyDown,
+    /// See [`TreeState::key_left`]
+    KeyLeft,
+    /// See [`TreeState::key_right`]
+    KeyRight,
+    /// See [`TreeState::select_first`]
+    SelectFirst,
+    /// See [`TreeState::select_last`]
+    SelectLast,
+    /// See [`TreeState::toggle_selected`]
+    ToggleSelected,
+    /// Open the selected node, see [`TreeState::open`]
+    OpenSelected,
+    /// Close the selected node, see [`TreeState::close`]
+    CloseSelected,
+    /// See [`TreeState::open_all`]
+    OpenAll,
+    /// See [`TreeState::close_all`]
+    CloseAll,
+    /// See [`TreeState::select_parent`]
+    SelectParent,
+    /// See [`TreeState::select_next_sibling`]
+    SelectNextSibling,
+    /// See [`TreeState::select_previous_sibling`]
+    SelectPreviousSibling,
+    /// See [`TreeState::page_up`]
+    PageUp,
+    /// See [`TreeState::page_down`]
+    PageDown,
+    /// See [`TreeState::half_page_up`]
+    HalfPageUp,
+    /// See [`TreeState::half_page_down`]
+    HalfPageDown,
+    /// Scroll the view up by one node, see [`TreeState::scroll_up`]
+    ScrollUp,
+    /// Scroll the view down by one node, see [`TreeState::scroll_down`]
+    ScrollDown,
+    /// See [`TreeState::click_at`]
+    Click { column: u16, row: u16 },
+    /// See [`TreeState::jump_back`]
+    JumpBack,
+    /// See [`TreeState::jump_forward`]
+    JumpForward,
+    /// Mark the selected node, see [`TreeState::set_mark`].
+    /// As the selection does not move it results in [`AtBoundary`](NavigationOutcome::AtBoundary).
+    SetMark(char),
+    /// See [`TreeState::jump_to_mark`]
+    JumpToMark(char),
+}
+
+impl TreeState {
+    /// Run the `action` on the state
+    pub fn apply_action(&mut self, items: &[TreeItem], action: TreeAction) -> NavigationOutcome {
+        match action {
+            TreeAction::KeyUp => self.key_up(items),
+            TreeAction::KeyDown => self.key_down(items),
+            TreeAction::KeyLeft => self.key_left(items),
+            TreeAction::KeyRight => self.key_right(items),
+            TreeAction::SelectFirst => self.select_first(items),
+            TreeAction::SelectLast => self.select_last(items),
+            TreeAction::ToggleSelected => {
+                if self.selected.is_empty() {
+                    NavigationOutcome::NothingToSelect
+                } else if self.close(&self.selected()) {
+                    NavigationOutcome::Closed
+                } else {
+                    self.open(self.selected());
+                    NavigationOutcome::Opened
+                }
+            }
+            TreeAction::OpenSelected => {
+                if self.selected.is_empty() {
+                    NavigationOutcome::NothingToSelect
+                } else if self.open(self.selected()) {
+                    NavigationOutcome::Opened
+                } else {
+                    NavigationOutcome::AtBoundary
+                }
+            }
+            TreeAction::CloseSelected => {
+                if self.selected.is_empty() {
+                    NavigationOutcome::NothingToSelect
+                } else if self.close(&self.selected()) {
+                    NavigationOutcome::Closed
+                } else {
+                    NavigationOutcome::AtBoundary
+                }
+            }
+            TreeAction::OpenAll => {
+                self.open_all(items);
+                NavigationOutcome::Opened
+            }
+            TreeAction::CloseAll => {
+                self.close_all();
+                NavigationOutcome::Closed
+            }
+            TreeAction::SelectParent => self.select_parent(),
+            TreeAction::SelectNextSibling => self.select_next_sibling(items),
+            TreeAction::SelectPreviousSibling => self.select_previous_sibling(items),
+            TreeAction::PageUp => self.page_up(items),
+            TreeAction::PageDown => self.page_down(items),
+            TreeAction::HalfPageUp => self.half_page_up(items),
+            TreeAction::HalfPageDown => self.half_page_down(items),
+            TreeAction::ScrollUp => {
+                if self.scroll_up(1) {
+                    NavigationOutcome::Moved
+                } else {
+                    NavigationOutcome::AtBoundary
+                }
+            }
+            TreeAction::ScrollDown => {
+                if self.scroll_down(items, 1) {
+                    NavigationOutcome::Moved
+                } else {
+                    NavigationOutcome::AtBoundary
+                }
+            }
+            TreeAction::Click { column, row } => self.click_at(column, row),
+            TreeAction::JumpBack => self.jump_back(),
+            TreeAction::JumpForward => self.jump_forward(),
+            TreeAction::SetMark(name) => {
+                if self.set_mark(name) {
+                    NavigationOutcome::AtBoundary
+                } else {
+                    NavigationOutcome::NothingToSelect
+                }
+            }
+            TreeAction::JumpToMark(name) => self.jump_to_mark(items, name),
+        }
+    }
+}
+
+/// Binds [`Key`s](Key) to [`TreeAction`s](TreeAction)
+///
+/// The default binds the arrow keys, Home, End, Enter and Space like the examples do.
+/// Mouse clicks select and wheel scrolling scrolls the view.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{Key, Keymap, NavigationOutcome, TreeAction, TreeItem, TreeState};
+/// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+/// let mut state = TreeState::default();
+/// let mut keymap = Keymap::default();
+/// keymap.bind(Key::Char('j'), TreeAction::KeyDown);
+///
+/// assert_eq!(
+///     keymap.handle_key(&mut state, &items, Key::Char('j')),
+///     Some((TreeAction::KeyDown, NavigationOutcome::Moved))
+/// );
+/// assert_eq!(state.selected(), [0]);
+/// assert_eq!(keymap.handle_key(&mut state, &items, Key::Char('x')), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: HashMap<Key, TreeAction>,
+    /// Buttons held down during the last `termwiz` mouse event, used to tell clicks from drags
+    #[cfg(feature = "termwiz")]
+    termwiz_buttons: termwiz::input::MouseButtons,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut keymap = Self::empty();
+        keymap.bind(Key::Up, TreeAction::KeyUp);
+        keymap.bind(Key::Down, TreeAction::KeyDown);
+        keymap.bind(Key::Left, TreeAction::KeyLeft);
+        keymap.bind(Key::Right, TreeAction::KeyRight);
+        keymap.bind(Key::Home, TreeAction::SelectFirst);
+        keymap.bind(Key::End, TreeAction::SelectLast);
+        keymap.bind(Key::Enter, TreeAction::ToggleSelected);
+        keymap.bind(Key::Char(' '), TreeAction::ToggleSelected);
+        keymap
+    }
+}
+
+impl Keymap {
+    /// Create a keymap without any bindings
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+            #[cfg(feature = "termwiz")]
+            termwiz_buttons: termwiz::input::MouseButtons::NONE,
+        }
+    }
+
+    /// Bind the `key` to the `action`.
+    /// Returns the action the `key` was bound to before.
+    pub fn bind(&mut self, key: Key, action: TreeAction) -> Option<TreeAction> {
+        self.bindings.insert(key, action)
+    }
+
+    /// Remove the binding of the `key`.
+    /// Returns the action the `key` was bound to.
+    pub fn unbind(&mut self, key: Key) -> Option<TreeAction> {
+        self.bindings.remove(&key)
+    }
+
+    #[must_use]
+    pub fn get(&self, key: Key) -> Option<TreeAction> {
+        self.bindings.get(&key).copied()
+    }
+
+    /// Run the action bound to the `key`.
+    /// Returns the action and its outcome when the `key` was consumed.
+    pub fn handle_key(
+        &self,
+        state: &mut TreeState,
+        items: &[TreeItem],
+        key: Key,
+    ) -> Option<(TreeAction, NavigationOutcome)> {
+        let action = self.get(key)?;
+        Some((action, state.apply_action(items, action)))
+    }
+
+    /// Run the action of the `mouse` event.
+    /// Returns the action and its outcome when the event was consumed.
+    /// Clicks are only consumed when they hit a node drawn by the last render.
+    #[allow(clippy::unused_self)]
+    pub fn handle_mouse(
+        &self,
+        state: &mut TreeState,
+        items: &[TreeItem],
+        mouse: MouseInput,
+    ) -> Option<(TreeAction, NavigationOutcome)> {
+        let action = match mouse {
+            MouseInput::Click { column, row } => {
+                state.identifier_at(column, row)?;
+                TreeAction::Click { column, row }
+            }
+            MouseInput::ScrollUp => TreeAction::ScrollUp,
+            MouseInput::ScrollDown => TreeAction::ScrollDown,
+        };
+        Some((action, state.apply_action(items, action)))
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl Key {
+    /// Convert a `crossterm` key event.
+    /// Returns `None` for key releases and keys without an equivalent.
+    #[must_use]
+    pub fn from_crossterm(event: crossterm::event::KeyEvent) -> Option<Self> {
+        use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+        if event.kind == KeyEventKind::Release {
+            return None;
+        }
+        let key = match event.code {
+            KeyCode::Char(c) if event.modifiers.contains(KeyModifiers::CONTROL) => Self::Ctrl(c),
+            KeyCode::Char(c) if event.modifiers.contains(KeyModifiers::ALT) => Self::Alt(c),
+            KeyCode::Char('\n') | KeyCode::Enter => Self::Enter,
+            KeyCode::Char(c) => Self::Char(c),
+            KeyCode::Up => Self::Up,
+            KeyCode::Down => Self::Down,
+            KeyCode::Left => Self::Left,
+            KeyCode::Right => Self::Right,
+            KeyCode::Home => Self::Home,
+            KeyCode::End => Self::End,
+            KeyCode::PageUp => Self::PageUp,
+            KeyCode::PageDown => Self::PageDown,
+            KeyCode::Esc => Self::Esc,
+            KeyCode::Backspace => Self::Backspace,
+            KeyCode::Tab => Self::Tab,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl MouseInput {
+    /// Convert a `crossterm` mouse event.
+    /// Returns `None` for events other than left clicks and wheel scrolling.
+    #[must_use]
+    pub const fn from_crossterm(event: crossterm::event::MouseEvent) -> Option<Self> {
+        use crossterm::event::{MouseButton, MouseEventKind};
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(Self::Click {
+                column: event.column,
+                row: event.row,
+            }),
+            MouseEventKind::ScrollUp => Some(Self::ScrollUp),
+            MouseEventKind::ScrollDown => Some(Self::ScrollDown),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl Keymap {
+    /// Run the action bound to a `crossterm` key or mouse event.
+    /// Returns the action and its outcome when the event was consumed.
+    pub fn handle_crossterm(
+        &self,
+        state: &mut TreeState,
+        items: &[TreeItem],
+        event: &crossterm::event::Event,
+    ) -> Option<(TreeAction, NavigationOutcome)> {
+        use crossterm::event::Event;
+        match event {
+            Event::Key(key) => self.handle_key(state, items, Key::from_crossterm(*key)?),
+            Event::Mouse(mouse) => {
+                self.handle_mouse(state, items, MouseInput::from_crossterm(*mouse)?)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(feature = "termion", unix))]
+impl Key {
+    /// Convert a `termion` key.
+    /// Returns `None` for keys without an equivalent.
+    #[must_use]
+    pub const fn from_termion(key: termion::event::Key) -> Option<Self> {
+        use termion::event::Key as TermionKey;
+        let key = match key {
+            TermionKey::Char('\n') => Self::Enter,
+            TermionKey::Char('\t') => Self::Tab,
+            TermionKey::Char(c) => Self::Char(c),
+            TermionKey::Ctrl(c) => Self::Ctrl(c),
+            TermionKey::Alt(c) => Self::Alt(c),
+            TermionKey::Up => Self::Up,
+            TermionKey::Down => Self::Down,
+            TermionKey::Left => Self::Left,
+            TermionKey::Right => Self::Right,
+            TermionKey::Home => Self::Home,
+            TermionKey::End => Self::End,
+            TermionKey::PageUp => Self::PageUp,
+            TermionKey::PageDown => Self::PageDown,
+            TermionKey::Esc => Self::Esc,
+            TermionKey::Backspace => Self::Backspace,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+#[cfg(all(feature = "termion", unix))]
+impl MouseInput {
+    /// Convert a `termion` mouse event.
+    /// Its one-based position is converted to the zero-based one used by `tui`.
+    /// Returns `None` for events other than left clicks and wheel scrolling.
+    #[must_use]
+    pub const fn from_termion(event: termion::event::MouseEvent) -> Option<Self> {
+        use termion::event::{MouseButton, MouseEvent};
+        match event {
+            MouseEvent::Press(MouseButton::Left, column, row) => Some(Self::Click {
+                column: column.saturating_sub(1),
+                row: row.saturating_sub(1),
+            }),
+            MouseEvent::Press(MouseButton::WheelUp, _, _) => Some(Self::ScrollUp),
+            MouseEvent::Press(MouseButton::WheelDown, _, _) => Some(Self::ScrollDown),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(feature = "termion", unix))]
+impl Keymap {
+    /// Run the action bound to a `termion` key or mouse event.
+    /// Returns the action and its outcome when the event was consumed.
+    pub fn handle_termion(
+        &self,
+        state: &mut TreeState,
+        items: &[TreeItem],
+        event: &termion::event::Event,
+    ) -> Option<(TreeAction, NavigationOutcome)> {
+        use termion::event::Event;
+        match event {
+            Event::Key(key) => self.handle_key(state, items, Key::from_termion(*key)?),
+            Event::Mouse(mouse) => {
+                self.handle_mouse(state, items, MouseInput::from_termion(*mouse)?)
+            }
+            Event::Unsupported(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "termwiz")]
+impl Key {
+    /// Convert a `termwiz` key event.
+    /// Returns `None` for keys without an equivalent.
+    #[must_use]
+    pub fn from_termwiz(event: &termwiz::input::KeyEvent) -> Option<Self> {
+        use termwiz::input::{KeyCode, Modifiers};
+        let key = match event.key {
+            KeyCode::Char(c) if event.modifiers.contains(Modifiers::CTRL) => Self::Ctrl(c),
+            KeyCode::Char(c) if event.modifiers.contains(Modifiers::ALT) => Self::Alt(c),
+            KeyCode::Char(c) => Self::Char(c),
+            KeyCode::UpArrow => Self::Up,
+            KeyCode::DownArrow => Self::Down,
+            KeyCode::LeftArrow => Self::Left,
+            KeyCode::RightArrow => Self::Right,
+            KeyCode::Home => Self::Home,
+            KeyCode::End => Self::End,
+            KeyCode::PageUp => Self::PageUp,
+            KeyCode::PageDown => Self::PageDown,
+            KeyCode::Enter => Self::Enter,
+            KeyCode::Escape => Self::Esc,
+            KeyCode::Backspace => Self::Backspace,
+            KeyCode::Tab => Self::Tab,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+#[cfg(feature = "termwiz")]
+impl MouseInput {
+    /// Convert a `termwiz` mouse event.
+    ///
+    /// `termwiz` reports the held buttons with every event including moves and drags.
+    /// Only pressing the left button while it was not held by the `previous` event is a click.
+    /// The one-based position reported by terminals is converted to the zero-based one used by `tui`.
+    /// Windows consoles report zero-based positions already.
+    ///
+    /// Returns `None` for events other than left clicks and vertical wheel scrolling.
+    #[must_use]
+    pub fn from_termwiz(
+        event: &termwiz::input::MouseEvent,
+        previous: &termwiz::input::MouseButtons,
+    ) -> Option<Self> {
+        use termwiz::input::MouseButtons;
+        let buttons = &event.mouse_buttons;
+        let offset = u16::from(!cfg!(windows));
+        if buttons.contains(MouseButtons::VERT_WHEEL) {
+            if buttons.contains(MouseButtons::WHEEL_POSITIVE) {
+                Some(Self::ScrollUp)
+            } else {
+                Some(Self::ScrollDown)
+            }
+        } else if buttons.contains(MouseButtons::LEFT) && !previous.contains(MouseButtons::LEFT) {
+            Some(Self::Click {
+                column: event.x.saturating_sub(offset),
+                row: event.y.saturating_sub(offset),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "termwiz")]
+impl Keymap {
+    /// Run the action bound to a `termwiz` key or mouse event.
+    /// Returns the action and its outcome when the event was consumed.
+    ///
+    /// The held mouse buttons are remembered in order to ignore drags, see [`MouseInput::from_termwiz`].
+    pub fn handle_termwiz(
+        &mut self,
+        state: &mut TreeState,
+        items: &[TreeItem],
+        event: &termwiz::input::InputEvent,
+    ) -> Option<(TreeAction, NavigationOutcome)> {
+        use termwiz::input::{InputEvent, MouseButtons};
+        match event {
+            InputEvent::Key(key) => self.handle_key(state, items, Key::from_termwiz(key)?),
+            InputEvent::Mouse(mouse) => {
+                let input = MouseInput::from_termwiz(mouse, &self.termwiz_buttons);
+                let buttons = &mouse.mouse_buttons;
+                if !buttons.intersects(MouseButtons::VERT_WHEEL | MouseButtons::HORZ_WHEEL) {
+                    self.termwiz_buttons = buttons.clone();
+                }
+                self.handle_mouse(state, items, input?)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn default_bindings() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let keymap = Keymap::default();
+    keymap.handle_key(&mut state, &items, Key::End);
+    assert_eq!(state.selected(), [2]);
+    keymap.handle_key(&mut state, &items, Key::Up);
+    assert_eq!(
+        keymap.handle_key(&mut state, &items, Key::Enter),
+        Some((TreeAction::ToggleSelected, NavigationOutcome::Opened))
+    );
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+    assert_eq!(keymap.handle_key(&mut state, &items, Key::Char('q')), None);
+}
+
+#[test]
+fn scroll_actions_report_boundaries() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    crate::render_example(&mut state, &items, 2);
+    assert_eq!(
+        state.apply_action(&items, TreeAction::ScrollUp),
+        NavigationOutcome::AtBoundary
+    );
+    assert_eq!(
+        state.apply_action(&items, TreeAction::ScrollDown),
+        NavigationOutcome::Moved
+    );
+    assert_eq!(
+        state.apply_action(&items, TreeAction::ScrollDown),
+        NavigationOutcome::AtBoundary
+    );
+    assert_eq!(state.get_offset(), 1);
+}
+
+#[test]
+fn rebind_and_unbind() {
+    let mut keymap = Keymap::default();
+    assert_eq!(
+        keymap.bind(Key::Enter, TreeAction::KeyRight),
+        Some(TreeAction::ToggleSelected)
+    );
+    assert_eq!(keymap.get(Key::Enter), Some(TreeAction::KeyRight));
+    assert_eq!(keymap.unbind(Key::Home), Some(TreeAction::SelectFirst));
+    assert_eq!(keymap.get(Key::Home), None);
+}
+
+#[cfg(feature = "crossterm")]
+#[test]
+fn crossterm_events() {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let keymap = Keymap::default();
+
+    let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    assert_eq!(
+        keymap.handle_crossterm(&mut state, &items, &down),
+        Some((TreeAction::KeyDown, NavigationOutcome::Moved))
+    );
+    assert_eq!(state.selected(), [0]);
+
+    let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert_eq!(Key::from_crossterm(ctrl_c), Some(Key::Ctrl('c')));
+    assert_eq!(
+        keymap.handle_crossterm(&mut state, &items, &Event::Key(ctrl_c)),
+        None
+    );
+
+    let scroll = Event::Mouse(MouseEvent {
+        kind: MouseEventKind::ScrollDown,
+        column: 0,
+        row: 0,
+        modifiers: KeyModifiers::NONE,
+    });
+    assert_eq!(
+        keymap.handle_crossterm(&mut state, &items, &scroll),
+        Some((TreeAction::ScrollDown, NavigationOutcome::Moved))
+    );
+    assert!(state.is_detached());
+}
+
+#[cfg(all(feature = "termion", unix))]
+#[test]
+fn termion_events() {
+    use termion::event::{Event, Key as TermionKey, MouseButton, MouseEvent};
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let keymap = Keymap::default();
+    assert_eq!(Key::from_termion(TermionKey::Char('\n')), Some(Key::Enter));
+    assert_eq!(
+        keymap.handle_termion(&mut state, &items, &Event::Key(TermionKey::End)),
+        Some((TreeAction::SelectLast, NavigationOutcome::Moved))
+    );
+    assert_eq!(state.selected(), [2]);
+
+    crate::render_example(&mut state, &items, 4);
+    let click = Event::Mouse(MouseEvent::Press(MouseButton::Left, 1, 2));
+    assert_eq!(
+        keymap.handle_termion(&mut state, &items, &click),
+        Some((
+            TreeAction::Click { column: 0, row: 1 },
+            NavigationOutcome::Moved
+        ))
+    );
+    assert_eq!(state.selected(), [1]);
+}
+
+#[cfg(feature = "termwiz")]
+#[test]
+fn termwiz_events() {
+    use termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent};
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let mut keymap = Keymap::default();
+    let end = InputEvent::Key(KeyEvent {
+        key: KeyCode::End,
+        modifiers: Modifiers::NONE,
+    });
+    assert_eq!(
+        keymap.handle_termwiz(&mut state, &items, &end),
+        Some((TreeAction::SelectLast, NavigationOutcome::Moved))
+    );
+
+    crate::render_example(&mut state, &items, 4);
+    let offset = u16::from(!cfg!(windows));
+    let click = InputEvent::Mouse(MouseEvent {
+        x: offset,
+        y: 1 + offset,
+        mouse_buttons: MouseButtons::LEFT,
+        modifiers: Modifiers::NONE,
+    });
+    assert_eq!(
+        keymap.handle_termwiz(&mut state, &items, &click),
+        Some((
+            TreeAction::Click { column: 0, row: 1 },
+            NavigationOutcome::Moved
+        ))
+    );
+    assert_eq!(state.selected(), [1]);
+    let wheel = MouseEvent {
+        x: 1,
+        y: 1,
+        mouse_buttons: MouseButtons::VERT_WHEEL | MouseButtons::WHEEL_POSITIVE,
+        modifiers: Modifiers::NONE,
+    };
+    assert_eq!(
+        MouseInput::from_termwiz(&wheel, &MouseButtons::NONE),
+        Some(MouseInput::ScrollUp)
+    );
+}
+
+#[cfg(feature = "termwiz")]
+#[test]
+fn termwiz_drag_is_no_click() {
+    use termwiz::input::{InputEvent, Modifiers, MouseButtons, MouseEvent};
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let mut keymap = Keymap::default();
+    crate::render_example(&mut state, &items, 4);
+    let offset = u16::from(!cfg!(windows));
+    let mouse = |row: u16, mouse_buttons: MouseButtons| {
+        InputEvent::Mouse(MouseEvent {
+            x: offset,
+            y: row + offset,
+            mouse_buttons,
+            modifiers: Modifiers::NONE,
+        })
+    };
+
+    assert!(keymap
+        .handle_termwiz(&mut state, &items, &mouse(0, MouseButtons::LEFT))
+        .is_some());
+    assert_eq!(state.selected(), [0]);
+    assert_eq!(
+        keymap.handle_termwiz(&mut state, &items, &mouse(1, MouseButtons::LEFT)),
+        None
+    );
+    assert_eq!(
+        keymap.handle_termwiz(&mut state, &items, &mouse(2, MouseButtons::LEFT)),
+        None
+    );
+    assert_eq!(state.selected(), [0]);
+
+    assert_eq!(
+        keymap.handle_termwiz(&mut state, &items, &mouse(2, MouseButtons::NONE)),
+        None
+    );
+    assert_eq!(
+        keymap.handle_termwiz(&mut state, &items, &mouse(2, MouseButtons::LEFT)),
+        Some((
+            TreeAction::Click { column: 0, row: 2 },
+            NavigationOutcome::Moved
+        ))
+    );
+    assert_eq!(state.selected(), [2]);
+}
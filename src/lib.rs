@@ -1,21 +1,46 @@
 #![forbid(unsafe_code)]
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::time::{Duration, Instant};
 
 use tui::buffer::Buffer;
 use tui::layout::{Corner, Rect};
-use tui::style::Style;
+use tui::style::{Modifier, Style};
 use tui::text::Text;
 use tui::widgets::{Block, StatefulWidget, Widget};
 use unicode_width::UnicodeWidthStr;
 
+mod clipboard;
+#[cfg(feature = "fs")]
+mod directory;
+mod export;
 mod flatten;
 mod identifier;
+mod keymap;
+mod outline;
+mod path_builder;
+mod text_input;
+#[cfg(any(feature = "serde_json", feature = "toml"))]
+mod value;
+mod vim;
 
+pub use crate::clipboard::{Clipboard, PastePosition};
+#[cfg(feature = "fs")]
+pub use crate::directory::{DirectoryEntry, DirectoryTree, EntryKind};
+pub use crate::export::PlainTextExport;
 pub use crate::flatten::{flatten, Flattened};
 pub use crate::identifier::{
     get_without_leaf as get_identifier_without_leaf, TreeIdentifier, TreeIdentifierVec,
 };
+pub use crate::keymap::{Key, Keymap, MouseInput, TreeAction};
+pub use crate::outline::{
+    parse_indented, parse_markdown, Indentation, OutlineError, OutlineErrorKind,
+};
+pub use crate::path_builder::PathTreeBuilder;
+pub use crate::text_input::TextInput;
+#[cfg(any(feature = "serde_json", feature = "toml"))]
+pub use crate::value::ValueTreeBuilder;
+pub use crate::vim::{VimKeymap, VimOutcome};
 
 /// Keeps the state of what is currently selected and what was opened in a [`Tree`]
 ///
@@ -25,11 +50,70 @@ pub use crate::identifier::{
 /// # use tui_tree_widget::TreeState;
 /// let mut state = TreeState::default();
 /// ```
+///
+/// With the `serde` feature the state can be persisted.
+/// The items might have changed in the meantime so consider to [`validate`](Self::validate) it after loading.
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::struct_excessive_bools)]
 pub struct TreeState {
     offset: usize,
     opened: HashSet<TreeIdentifierVec>,
     selected: TreeIdentifierVec,
+    /// Named marks, see [`set_mark`](Self::set_mark)
+    marks: BTreeMap<char, TreeIdentifierVec>,
+    /// The node being edited and its input
+    #[cfg_attr(feature = "serde", serde(skip))]
+    editing: Option<(TreeIdentifierVec, TextInput)>,
+    /// Position of the text cursor while editing, set by the last render
+    #[cfg_attr(feature = "serde", serde(skip))]
+    edit_cursor_position: Option<(u16, u16)>,
+    /// Height available to the items in the last render
+    #[cfg_attr(feature = "serde", serde(skip))]
+    viewport_height: usize,
+    /// The view was scrolled independently of the selection and is not scrolled back to it on render
+    #[cfg_attr(feature = "serde", serde(skip))]
+    detached: bool,
+    /// Scroll the selected node into the middle on the next render
+    #[cfg_attr(feature = "serde", serde(skip))]
+    center_once: bool,
+    /// Opening a node closes its open siblings
+    #[cfg_attr(feature = "serde", serde(skip))]
+    accordion: bool,
+    /// `key_up` and `key_down` continue at the other end
+    #[cfg_attr(feature = "serde", serde(skip))]
+    wrap_around: bool,
+    /// Characters typed for [`type_ahead`](Self::type_ahead) and the time of the last one
+    #[cfg_attr(feature = "serde", serde(skip))]
+    type_ahead: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    type_ahead_time: Option<Instant>,
+    /// Nodes shown by the last render and where they were drawn
+    #[cfg_attr(feature = "serde", serde(skip))]
+    rendered: Vec<RenderedNode>,
+    /// Positions before large jumps, see [`jump_back`](Self::jump_back)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    jumps: Vec<Jump>,
+    /// Index into `jumps` while moving through them, equal to its length otherwise
+    #[cfg_attr(feature = "serde", serde(skip))]
+    jump_index: usize,
+}
+
+/// Maximum amount of positions remembered for [`TreeState::jump_back`]
+const MAX_JUMPS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Jump {
+    selected: TreeIdentifierVec,
+    offset: usize,
+}
+
+#[derive(Debug, Clone)]
+struct RenderedNode {
+    area: Rect,
+    identifier: TreeIdentifierVec,
+    has_children: bool,
 }
 
 impl TreeState {
@@ -38,6 +122,40 @@ impl TreeState {
         self.offset
     }
 
+    /// Height available to the items in the last render of a [`Tree`].
+    /// Used for page wise navigation like [`page_down`](Self::page_down).
+    #[must_use]
+    pub const fn viewport_height(&self) -> usize {
+        self.viewport_height
+    }
+
+    #[must_use]
+    pub const fn is_accordion(&self) -> bool {
+        self.accordion
+    }
+
+    /// Only keep one branch per level open.
+    /// When enabled, opening a node closes its open siblings and their descendants.
+    ///
+    /// Bulk operations like [`open_all`](Self::open_all) are not affected.
+    /// This setting is not persisted with the `serde` feature.
+    pub fn set_accordion(&mut self, accordion: bool) {
+        self.accordion = accordion;
+    }
+
+    #[must_use]
+    pub const fn is_wrap_around(&self) -> bool {
+        self.wrap_around
+    }
+
+    /// Let [`key_down`](Self::key_down) on the last visible node select the first one
+    /// and [`key_up`](Self::key_up) on the first visible node select the last one.
+    ///
+    /// This setting is not persisted with the `serde` feature.
+    pub fn set_wrap_around(&mut self, wrap_around: bool) {
+        self.wrap_around = wrap_around;
+    }
+
     #[must_use]
     pub fn get_all_opened(&self) -> Vec<TreeIdentifierVec> {
         self.opened.iter().cloned().collect()
@@ -53,6 +171,7 @@ impl TreeState {
         I: Into<Vec<usize>>,
     {
         self.selected = identifier.into();
+        self.detached = false;
 
         // TODO: ListState does this. Is this relevant?
         if self.selected.is_empty() {
@@ -63,19 +182,46 @@ impl TreeState {
     /// Open a tree node.
     /// Returns `true` if the node was closed and has been opened.
     /// Returns `false` if the node was already open.
+    ///
+    /// In [accordion mode](Self::set_accordion) the open siblings of the node are closed.
     pub fn open(&mut self, identifier: TreeIdentifierVec) -> bool {
         if identifier.is_empty() {
             false
         } else {
+            if self.accordion {
+                self.close_siblings(&identifier);
+            }
             self.opened.insert(identifier)
         }
     }
 
+    /// Close all siblings of the node referenced by the `identifier` and their descendants.
+    /// When the selection is inside a closed sibling it moves to that sibling.
+    fn close_siblings(&mut self, identifier: TreeIdentifier) {
+        let (parent, index) = get_identifier_without_leaf(identifier);
+        let is_in_sibling = |other: &[usize]| {
+            other.len() >= identifier.len()
+                && other.starts_with(parent)
+                && Some(other[parent.len()]) != index
+        };
+        self.opened.retain(|other| !is_in_sibling(other));
+        if self.selected.len() > identifier.len() && is_in_sibling(&self.selected) {
+            self.select(self.selected[..identifier.len()].to_vec());
+        }
+    }
+
     /// Close a tree node.
     /// Returns `true` if the node was open and has been closed.
     /// Returns `false` if the node was already closed.
+    ///
+    /// When the selection is inside the closed node it moves to the closed node in order to stay visible.
     pub fn close(&mut self, identifier: TreeIdentifier) -> bool {
-        self.opened.remove(identifier)
+        let closed = self.opened.remove(identifier);
+        if closed && self.selected.len() > identifier.len() && self.selected.starts_with(identifier)
+        {
+            self.select(identifier);
+        }
+        closed
     }
 
     /// Toggles a tree node.
@@ -94,75 +240,958 @@ impl TreeState {
         self.toggle(self.selected());
     }
 
+    /// Close all nodes.
+    /// The selection moves to the top level node containing it in order to stay visible.
     pub fn close_all(&mut self) {
         self.opened.clear();
+        self.selected.truncate(1);
+    }
+
+    /// Open all ancestors of the node referenced by the `identifier` so it becomes visible.
+    /// Returns `false` when the `identifier` does not reference an item.
+    pub fn reveal(&mut self, items: &[TreeItem], identifier: TreeIdentifier) -> bool {
+        if get_item(items, identifier).is_none() {
+            return false;
+        }
+        for depth in 1..identifier.len() {
+            self.open(identifier[..depth].to_vec());
+        }
+        true
+    }
+
+    /// Select the node referenced by the `identifier` and open all its ancestors.
+    /// The next render scrolls it into view.
+    /// Returns `false` and keeps the selection when the `identifier` does not reference an item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// assert!(state.select_and_reveal(&items, &[0, 0]));
+    /// assert_eq!(state.get_all_opened(), [vec![0]]);
+    /// ```
+    pub fn select_and_reveal(&mut self, items: &[TreeItem], identifier: TreeIdentifier) -> bool {
+        if get_item(items, identifier).is_none() {
+            return false;
+        }
+        // Record first as revealing might close the selected node in accordion mode
+        if self.selected != identifier {
+            self.record_jump();
+        }
+        self.reveal(items, identifier);
+        self.select(identifier);
+        true
+    }
+
+    /// Open all nodes which have children.
+    pub fn open_all(&mut self, items: &[TreeItem]) {
+        self.open_recursive(items, &[], usize::MAX);
+    }
+
+    /// Open all nodes with children up to the given `depth` and close all deeper ones.
+    /// With a `depth` of `0` everything is closed, with `1` the top level nodes are opened and so on.
+    ///
+    /// The selection moves to its parent at the deepest visible level in order to stay visible.
+    pub fn set_open_depth(&mut self, items: &[TreeItem], depth: usize) {
+        self.opened.retain(|identifier| identifier.len() <= depth);
+        self.open_recursive(items, &[], depth);
+        if self.selected.len() > depth + 1 {
+            self.select(self.selected[..=depth].to_vec());
+        }
+    }
+
+    /// Open the selected node and all its descendants which have children.
+    pub fn open_selected_recursive(&mut self, items: &[TreeItem]) {
+        if let Some(item) = get_item(items, &self.selected) {
+            if !item.children.is_empty() {
+                self.opened.insert(self.selected.clone());
+            }
+            let selected = self.selected.clone();
+            self.open_recursive(&item.children, &selected, usize::MAX);
+        }
+    }
+
+    /// Close the selected node and all its descendants.
+    pub fn close_selected_recursive(&mut self) {
+        let selected = &self.selected;
+        if !selected.is_empty() {
+            self.opened
+                .retain(|identifier| !identifier.starts_with(selected));
+        }
+    }
+
+    /// Close all nodes except the ones containing the selected node.
+    pub fn close_all_except_selected(&mut self) {
+        let selected = &self.selected;
+        self.opened.retain(|identifier| {
+            identifier.len() < selected.len() && selected.starts_with(identifier)
+        });
+    }
+
+    /// Open all `items` with children up to a depth of `max_depth`.
+    /// The `items` are the children of the node referenced by `parent`.
+    fn open_recursive(&mut self, items: &[TreeItem], parent: TreeIdentifier, max_depth: usize) {
+        if parent.len() >= max_depth {
+            return;
+        }
+        for (index, item) in items.iter().enumerate() {
+            if item.children.is_empty() {
+                continue;
+            }
+            let mut identifier = parent.to_vec();
+            identifier.push(index);
+            self.open_recursive(&item.children, &identifier, max_depth);
+            self.opened.insert(identifier);
+        }
+    }
+
+    /// Mark the selected node with the `name` like `ma` in vim.
+    /// A mark with the same `name` is replaced.
+    /// Returns `false` when nothing is selected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.select_and_reveal(&items, &[0, 0]);
+    /// state.set_mark('m');
+    /// state.close_all();
+    ///
+    /// state.jump_to_mark(&items, 'm');
+    /// assert_eq!(state.selected(), [0, 0]);
+    /// ```
+    pub fn set_mark(&mut self, name: char) -> bool {
+        if self.selected.is_empty() {
+            false
+        } else {
+            self.marks.insert(name, self.selected.clone());
+            true
+        }
+    }
+
+    #[must_use]
+    pub fn mark(&self, name: char) -> Option<&[usize]> {
+        self.marks.get(&name).map(Vec::as_slice)
+    }
+
+    /// All marks ordered by their name
+    #[must_use]
+    pub fn marks(&self) -> Vec<(char, TreeIdentifierVec)> {
+        self.marks
+            .iter()
+            .map(|(name, identifier)| (*name, identifier.clone()))
+            .collect()
+    }
+
+    /// Select the node marked with the `name` and open its ancestors like `'a` in vim.
+    /// The position before is remembered for [`jump_back`](Self::jump_back).
+    ///
+    /// Returns [`NothingToSelect`](NavigationOutcome::NothingToSelect) when there is no such mark or its node does not exist anymore.
+    pub fn jump_to_mark(&mut self, items: &[TreeItem], name: char) -> NavigationOutcome {
+        let identifier = match self.marks.get(&name) {
+            Some(identifier) => identifier.clone(),
+            None => return NavigationOutcome::NothingToSelect,
+        };
+        if identifier == self.selected {
+            NavigationOutcome::AtBoundary
+        } else if self.select_and_reveal(items, &identifier) {
+            NavigationOutcome::Moved
+        } else {
+            NavigationOutcome::NothingToSelect
+        }
+    }
+
+    /// Remove the mark with the `name`.
+    /// Returns `true` when it existed.
+    pub fn clear_mark(&mut self, name: char) -> bool {
+        self.marks.remove(&name).is_some()
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marks.clear();
+    }
+
+    /// Point the edited node, the marks and the jump list to where `remap` moved the nodes.
+    /// When `remap` returns `None` the node is gone: editing is cancelled, its marks are removed
+    /// and jumps to it are forgotten.
+    ///
+    /// Used when the items change in a known way like [`Clipboard::cut`].
+    fn remap_identifiers<F>(&mut self, mut remap: F)
+    where
+        F: FnMut(TreeIdentifier) -> Option<TreeIdentifierVec>,
+    {
+        if let Some((identifier, input)) = self.editing.take() {
+            match remap(&identifier) {
+                Some(identifier) => self.editing = Some((identifier, input)),
+                None => self.edit_cursor_position = None,
+            }
+        }
+
+        self.marks = std::mem::take(&mut self.marks)
+            .into_iter()
+            .filter_map(|(name, marked)| Some((name, remap(&marked)?)))
+            .collect();
+
+        let jump_index = self.jump_index;
+        let mut jumps = Vec::with_capacity(self.jumps.len());
+        for (index, jump) in std::mem::take(&mut self.jumps).into_iter().enumerate() {
+            match remap(&jump.selected) {
+                Some(selected) => jumps.push(Jump {
+                    selected,
+                    offset: jump.offset,
+                }),
+                None if index < jump_index => self.jump_index -= 1,
+                None => {}
+            }
+        }
+        self.jumps = jumps;
+    }
+
+    /// Drop opened nodes, marks, jumps, the edit session and the selection when they do not reference an item of `items`.
+    /// Returns `true` when something was dropped.
+    ///
+    /// Useful after loading a persisted state while the items might have changed.
+    /// See [`reconcile`](Self::reconcile) to move the selection to a close node instead.
+    pub fn validate(&mut self, items: &[TreeItem]) -> bool {
+        let opened_before = self.opened.len();
+        self.opened
+            .retain(|identifier| get_item(items, identifier).is_some());
+        let marks_before = self.marks.len();
+        let was_editing = self.editing.is_some();
+        self.remap_identifiers(|identifier| {
+            get_item(items, identifier).map(|_| identifier.to_vec())
+        });
+        let mut changed = opened_before != self.opened.len()
+            || marks_before != self.marks.len()
+            || was_editing != self.editing.is_some();
+
+        if !self.selected.is_empty() && get_item(items, &self.selected).is_none() {
+            self.select(Vec::new());
+            changed = true;
+        }
+        changed
+    }
+
+    /// Repair the state after the `items` changed.
+    ///
+    /// - The selection moves to the closest existing node: a sibling at the same position or before it, otherwise the parent.
+    /// - Opened nodes which do not exist anymore or have no children are removed.
+    /// - Marks of nodes which do not exist anymore are removed.
+    /// - Jumps to nodes which do not exist anymore are forgotten and editing them is cancelled.
+    /// - The offset is limited to the visible nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let mut state = TreeState::default();
+    /// state.select(vec![0, 3]);
+    ///
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let report = state.reconcile(&items);
+    /// assert_eq!(state.selected(), [0, 0]);
+    /// assert_eq!(report.selection_moved, Some((vec![0, 3], vec![0, 0])));
+    /// ```
+    pub fn reconcile(&mut self, items: &[TreeItem]) -> ReconcileReport {
+        let mut report = ReconcileReport::default();
+
+        let mut removed_opened = self
+            .opened
+            .iter()
+            .filter(|identifier| {
+                get_item(items, identifier).map_or(true, |item| item.children.is_empty())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        removed_opened.sort();
+        for identifier in &removed_opened {
+            self.opened.remove(identifier);
+        }
+        report.removed_opened = removed_opened;
+
+        let (marks, removed_marks) = std::mem::take(&mut self.marks)
+            .into_iter()
+            .partition(|(_, identifier)| get_item(items, identifier).is_some());
+        self.marks = marks;
+        report.removed_marks = removed_marks.into_iter().collect();
+        self.remap_identifiers(|identifier| {
+            get_item(items, identifier).map(|_| identifier.to_vec())
+        });
+
+        if !self.selected.is_empty() && get_item(items, &self.selected).is_none() {
+            let before = self.selected.clone();
+            let existing = (0..before.len())
+                .rev()
+                .find(|depth| get_item(items, &before[..*depth]).is_some())
+                .unwrap_or(0);
+            let mut selected = before[..existing].to_vec();
+            let siblings = get_item(items, &selected).map_or(items, |item| &item.children);
+            if !siblings.is_empty() {
+                selected.push(before[existing].min(siblings.len() - 1));
+            }
+            self.select(selected.clone());
+            report.selection_moved = Some((before, selected));
+        }
+
+        let visible = flatten(&self.get_all_opened(), items).len();
+        let max_offset = visible.saturating_sub(1);
+        if self.offset > max_offset {
+            self.offset = max_offset;
+            report.offset_clamped = true;
+        }
+
+        report
     }
 
     /// Select the first node.
-    pub fn select_first(&mut self) {
-        self.select(vec![0]);
+    pub fn select_first(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        let visible = flatten(&self.get_all_opened(), items);
+        self.jump_to_flattened(visible.first())
     }
 
     /// Select the last node.
-    pub fn select_last(&mut self, items: &[TreeItem]) {
+    pub fn select_last(&mut self, items: &[TreeItem]) -> NavigationOutcome {
         let visible = flatten(&self.get_all_opened(), items);
-        let new_identifier = visible
-            .last()
-            .map(|o| o.identifier.clone())
-            .unwrap_or_default();
-        self.select(new_identifier);
+        self.jump_to_flattened(visible.last())
     }
 
     /// Handles the up arrow key.
     /// Moves up in the current depth or to its parent.
-    pub fn key_up(&mut self, items: &[TreeItem]) {
-        let visible = flatten(&self.get_all_opened(), items);
-        let current_identifier = self.selected();
-        let current_index = visible
-            .iter()
-            .position(|o| o.identifier == current_identifier);
-        let new_index = current_index.map_or(0, |current_index| {
-            current_index.saturating_sub(1).min(visible.len() - 1)
-        });
-        let new_identifier = visible
-            .get(new_index)
-            .map(|o| o.identifier.clone())
-            .unwrap_or_default();
-        self.select(new_identifier);
+    /// See [`set_wrap_around`](Self::set_wrap_around) for moving from the first to the last node.
+    pub fn key_up(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        let wrap_around = self.wrap_around;
+        self.select_visible_index(items, |visible, current| {
+            if wrap_around && current == 0 {
+                visible.len() - 1
+            } else {
+                current.saturating_sub(1)
+            }
+        })
     }
 
     /// Handles the down arrow key.
     /// Moves down in the current depth or into a child node.
-    pub fn key_down(&mut self, items: &[TreeItem]) {
-        let visible = flatten(&self.get_all_opened(), items);
-        let current_identifier = self.selected();
-        let current_index = visible
-            .iter()
-            .position(|o| o.identifier == current_identifier);
-        let new_index = current_index.map_or(0, |current_index| {
-            current_index.saturating_add(1).min(visible.len() - 1)
-        });
-        let new_identifier = visible
-            .get(new_index)
-            .map(|o| o.identifier.clone())
-            .unwrap_or_default();
-        self.select(new_identifier);
+    /// See [`set_wrap_around`](Self::set_wrap_around) for moving from the last to the first node.
+    pub fn key_down(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        let wrap_around = self.wrap_around;
+        self.select_visible_index(items, |visible, current| {
+            if wrap_around && current + 1 == visible.len() {
+                0
+            } else {
+                current.saturating_add(1)
+            }
+        })
     }
 
     /// Handles the left arrow key.
     /// Closes the currently selected or moves to its parent.
-    pub fn key_left(&mut self) {
+    ///
+    /// A selection which does not reference a node in `items` is moved to the closest visible ancestor instead.
+    pub fn key_left(&mut self, items: &[TreeItem]) -> NavigationOutcome {
         let selected = self.selected();
-        if !self.close(&selected) {
+        if selected.is_empty() {
+            NavigationOutcome::NothingToSelect
+        } else if get_item(items, &selected).is_none() {
+            self.select_visible_index(items, |_, current| current)
+        } else if self.close(&selected) {
+            NavigationOutcome::Closed
+        } else if selected.len() == 1 {
+            NavigationOutcome::AtBoundary
+        } else {
             let (head, _) = get_identifier_without_leaf(&selected);
             self.select(head);
+            NavigationOutcome::Moved
         }
     }
 
     /// Handles the right arrow key.
-    /// Opens the currently selected.
-    pub fn key_right(&mut self) {
-        self.open(self.selected());
+    /// Opens the currently selected. Leaves can not be opened and report [`NavigationOutcome::AtBoundary`].
+    ///
+    /// A selection which does not reference a node in `items` is moved to the closest visible ancestor instead.
+    pub fn key_right(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        if self.selected.is_empty() {
+            return NavigationOutcome::NothingToSelect;
+        }
+        match get_item(items, &self.selected) {
+            None => self.select_visible_index(items, |_, current| current),
+            Some(item) if item.children.is_empty() => NavigationOutcome::AtBoundary,
+            Some(_) => {
+                if self.open(self.selected()) {
+                    NavigationOutcome::Opened
+                } else {
+                    NavigationOutcome::AtBoundary
+                }
+            }
+        }
+    }
+
+    /// Scroll the view up by `amount` nodes without changing the selection.
+    /// Returns `false` when the view is already at the top.
+    ///
+    /// The view stays detached from the selection until the selection changes.
+    /// Until then the render does not scroll back to the selected node.
+    pub fn scroll_up(&mut self, amount: usize) -> bool {
+        let before = self.offset;
+        self.offset = self.offset.saturating_sub(amount);
+        self.detached = true;
+        before != self.offset
+    }
+
+    /// Scroll the view down by `amount` nodes without changing the selection.
+    /// Returns `false` when the last node is already at the bottom of the last rendered height.
+    ///
+    /// The view stays detached from the selection until the selection changes.
+    /// Until then the render does not scroll back to the selected node.
+    pub fn scroll_down(&mut self, items: &[TreeItem], amount: usize) -> bool {
+        let before = self.offset;
+        self.offset = self
+            .offset
+            .saturating_add(amount)
+            .min(self.get_max_offset(items));
+        self.detached = true;
+        self.offset > before
+    }
+
+    /// Largest offset which still fills the last rendered height, so the last node is at the bottom
+    fn get_max_offset(&self, items: &[TreeItem]) -> usize {
+        let visible = flatten(&self.get_all_opened(), items);
+        get_earliest_start(&visible, visible.len(), self.viewport_height)
+            .min(visible.len().saturating_sub(1))
+    }
+
+    /// Identifier of the node drawn at the given terminal position by the last render of a [`Tree`]
+    #[must_use]
+    pub fn identifier_at(&self, column: u16, row: u16) -> Option<&[usize]> {
+        self.rendered_at(column, row)
+            .map(|node| node.identifier.as_slice())
+    }
+
+    fn rendered_at(&self, column: u16, row: u16) -> Option<&RenderedNode> {
+        self.rendered.iter().find(|node| {
+            let area = node.area;
+            area.left() <= column
+                && column < area.right()
+                && area.top() <= row
+                && row < area.bottom()
+        })
+    }
+
+    /// Handle a mouse click at the given terminal position.
+    /// Selects the node drawn there by the last render of a [`Tree`].
+    /// A click on the already selected node toggles it.
+    ///
+    /// Returns [`NothingToSelect`](NavigationOutcome::NothingToSelect) when no node was drawn there.
+    pub fn click_at(&mut self, column: u16, row: u16) -> NavigationOutcome {
+        match self.rendered_at(column, row).cloned() {
+            None => NavigationOutcome::NothingToSelect,
+            Some(node) if node.identifier != self.selected => {
+                self.record_jump();
+                self.select(node.identifier);
+                NavigationOutcome::Moved
+            }
+            Some(node) if !node.has_children => NavigationOutcome::AtBoundary,
+            Some(node) => {
+                if self.close(&node.identifier) {
+                    NavigationOutcome::Closed
+                } else {
+                    self.open(node.identifier);
+                    NavigationOutcome::Opened
+                }
+            }
+        }
+    }
+
+    /// Scroll the selected node into the middle of the view on the next render.
+    pub fn center_on_selected(&mut self) {
+        self.detached = false;
+        self.center_once = true;
+    }
+
+    /// Returns `true` when the view was scrolled independently of the selection.
+    /// See [`scroll_down`](Self::scroll_down).
+    #[must_use]
+    pub const fn is_detached(&self) -> bool {
+        self.detached
+    }
+
+    /// Move the selection down by the height of the last rendered viewport and scroll by the same amount.
+    pub fn page_down(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.move_by_height(items, self.viewport_height, true)
+    }
+
+    /// Move the selection up by the height of the last rendered viewport and scroll by the same amount.
+    pub fn page_up(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.move_by_height(items, self.viewport_height, false)
+    }
+
+    /// Move the selection down by half the height of the last rendered viewport and scroll by the same amount.
+    pub fn half_page_down(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.move_by_height(items, self.viewport_height / 2, true)
+    }
+
+    /// Move the selection up by half the height of the last rendered viewport and scroll by the same amount.
+    pub fn half_page_up(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.move_by_height(items, self.viewport_height / 2, false)
+    }
+
+    /// Move the selection by as many visible nodes as fit into `height` rows, but at least one.
+    /// The offset is moved by the same amount of nodes but not beyond the last full page.
+    fn move_by_height(
+        &mut self,
+        items: &[TreeItem],
+        height: usize,
+        down: bool,
+    ) -> NavigationOutcome {
+        let mut moved = 0;
+        let outcome = self.select_visible_index(items, |visible, current| {
+            let mut index = current;
+            let mut used = 0;
+            loop {
+                let next = if down {
+                    index.checked_add(1).filter(|next| *next < visible.len())
+                } else {
+                    index.checked_sub(1)
+                };
+                let next = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                used += visible[next].item.height();
+                if used > height && index != current {
+                    break;
+                }
+                index = next;
+            }
+            moved = if down {
+                index - current
+            } else {
+                current - index
+            };
+            index
+        });
+        if outcome == NavigationOutcome::Moved {
+            self.offset = if down {
+                self.offset
+                    .saturating_add(moved)
+                    .min(self.get_max_offset(items))
+            } else {
+                self.offset.saturating_sub(moved)
+            };
+        }
+        outcome
+    }
+
+    /// Select the next sibling of the selected node skipping its children.
+    pub fn select_next_sibling(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.select_sibling(items, |index, _| index.checked_add(1))
+    }
+
+    /// Select the previous sibling of the selected node.
+    pub fn select_previous_sibling(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.select_sibling(items, |index, _| index.checked_sub(1))
+    }
+
+    /// Select the first sibling of the selected node.
+    pub fn select_first_sibling(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.select_sibling(items, |_, _| Some(0))
+    }
+
+    /// Select the last sibling of the selected node.
+    pub fn select_last_sibling(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.select_sibling(items, |_, amount| amount.checked_sub(1))
+    }
+
+    /// Select the parent of the selected node.
+    pub fn select_parent(&mut self) -> NavigationOutcome {
+        match self.selected.len() {
+            0 => NavigationOutcome::NothingToSelect,
+            1 => NavigationOutcome::AtBoundary,
+            _ => {
+                let (parent, _) = get_identifier_without_leaf(&self.selected);
+                self.select(parent.to_vec());
+                NavigationOutcome::Moved
+            }
+        }
+    }
+
+    /// Select the first child of the selected node and open it when needed.
+    pub fn select_first_child(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.select_child(items, |_| 0)
+    }
+
+    /// Select the last child of the selected node and open it when needed.
+    pub fn select_last_child(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        self.select_child(items, |amount| amount - 1)
+    }
+
+    /// Select the next sibling of the parent of the selected node.
+    pub fn select_parent_next_sibling(&mut self, items: &[TreeItem]) -> NavigationOutcome {
+        let before = self.selected.clone();
+        match self.select_parent() {
+            NavigationOutcome::Moved => {}
+            outcome => return outcome,
+        }
+        let outcome = self.select_next_sibling(items);
+        if outcome != NavigationOutcome::Moved {
+            self.selected = before;
+        }
+        outcome
+    }
+
+    /// Return to the position before the last large jump like [`select_last`](Self::select_last),
+    /// [`select_and_reveal`](Self::select_and_reveal), [`type_ahead`](Self::type_ahead) or [`click_at`](Self::click_at).
+    /// The selection, its closed ancestors and the offset are restored.
+    ///
+    /// The last 100 jumps are remembered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{NavigationOutcome, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b"), TreeItem::new_leaf("c")];
+    /// let mut state = TreeState::default();
+    /// state.select(vec![1]);
+    /// state.select_last(&items);
+    ///
+    /// assert_eq!(state.jump_back(), NavigationOutcome::Moved);
+    /// assert_eq!(state.selected(), [1]);
+    /// assert_eq!(state.jump_forward(), NavigationOutcome::Moved);
+    /// assert_eq!(state.selected(), [2]);
+    /// ```
+    pub fn jump_back(&mut self) -> NavigationOutcome {
+        if self.jump_index == self.jumps.len() {
+            // Remember the current position so jump_forward can return to it
+            let current = self.current_jump();
+            if self.jumps.last() != Some(&current) {
+                self.push_jump(current);
+            }
+            self.jump_index = self.jumps.len() - 1;
+        }
+        if self.jump_index == 0 {
+            return NavigationOutcome::AtBoundary;
+        }
+        self.jump_index -= 1;
+        self.restore_jump();
+        NavigationOutcome::Moved
+    }
+
+    /// Undo a [`jump_back`](Self::jump_back).
+    pub fn jump_forward(&mut self) -> NavigationOutcome {
+        if self.jump_index + 1 >= self.jumps.len() {
+            return NavigationOutcome::AtBoundary;
+        }
+        self.jump_index += 1;
+        self.restore_jump();
+        NavigationOutcome::Moved
+    }
+
+    fn current_jump(&self) -> Jump {
+        Jump {
+            selected: self.selected.clone(),
+            offset: self.offset,
+        }
+    }
+
+    /// Remember the current position before a large jump.
+    /// Positions which were jumped back from are forgotten.
+    fn record_jump(&mut self) {
+        if self.selected.is_empty() {
+            return;
+        }
+        self.jumps.truncate(self.jump_index);
+        let current = self.current_jump();
+        if self.jumps.last() != Some(&current) {
+            self.push_jump(current);
+        }
+        self.jump_index = self.jumps.len();
+    }
+
+    fn push_jump(&mut self, jump: Jump) {
+        self.jumps.push(jump);
+        if self.jumps.len() > MAX_JUMPS {
+            self.jumps.remove(0);
+            self.jump_index = self.jump_index.saturating_sub(1);
+        }
+    }
+
+    fn restore_jump(&mut self) {
+        let jump = self.jumps[self.jump_index].clone();
+        for depth in 1..jump.selected.len() {
+            self.open(jump.selected[..depth].to_vec());
+        }
+        self.select(jump.selected);
+        self.offset = jump.offset;
+    }
+
+    /// Like [`select_flattened`](Self::select_flattened) but remember the position before for [`jump_back`](Self::jump_back).
+    fn jump_to_flattened(&mut self, target: Option<&Flattened>) -> NavigationOutcome {
+        if target.map_or(false, |target| target.identifier != self.selected) {
+            self.record_jump();
+        }
+        self.select_flattened(target)
+    }
+
+    /// Select the sibling at the index returned by `new_index`.
+    /// It is called with the index of the selected node and the amount of siblings including itself.
+    fn select_sibling<F>(&mut self, items: &[TreeItem], new_index: F) -> NavigationOutcome
+    where
+        F: FnOnce(usize, usize) -> Option<usize>,
+    {
+        let (parent, index) = match get_identifier_without_leaf(&self.selected) {
+            (parent, Some(index)) if get_item(items, &self.selected).is_some() => (parent, index),
+            _ => return NavigationOutcome::NothingToSelect,
+        };
+        let siblings = get_item(items, parent).map_or(items, |item| &item.children);
+        match new_index(index, siblings.len()) {
+            Some(new_index) if new_index != index && new_index < siblings.len() => {
+                let mut identifier = parent.to_vec();
+                identifier.push(new_index);
+                self.select(identifier);
+                NavigationOutcome::Moved
+            }
+            _ => NavigationOutcome::AtBoundary,
+        }
+    }
+
+    /// Open the selected node and select the child at the index returned by `new_index`.
+    /// It is called with the amount of children which is at least one.
+    fn select_child<F>(&mut self, items: &[TreeItem], new_index: F) -> NavigationOutcome
+    where
+        F: FnOnce(usize) -> usize,
+    {
+        let amount = match get_item(items, &self.selected) {
+            None => return NavigationOutcome::NothingToSelect,
+            Some(item) if item.children.is_empty() => return NavigationOutcome::AtBoundary,
+            Some(item) => item.children.len(),
+        };
+        let mut identifier = self.selected();
+        self.open(identifier.clone());
+        identifier.push(new_index(amount).min(amount - 1));
+        self.select(identifier);
+        NavigationOutcome::Moved
+    }
+
+    /// Select the visible node at the index returned by `new_index`.
+    /// It is called with the visible nodes and the index of the currently selected one.
+    ///
+    /// When nothing or an invisible node is selected, its closest visible parent or the first node is selected instead.
+    fn select_visible_index<F>(&mut self, items: &[TreeItem], new_index: F) -> NavigationOutcome
+    where
+        F: FnOnce(&[Flattened], usize) -> usize,
+    {
+        let visible = flatten(&self.get_all_opened(), items);
+        let last = match visible.len().checked_sub(1) {
+            Some(last) => last,
+            None => return self.select_flattened(None),
+        };
+        let index = match visible.iter().position(|o| o.identifier == self.selected) {
+            Some(current) => new_index(&visible, current).min(last),
+            None => (1..self.selected.len())
+                .rev()
+                .find_map(|depth| {
+                    visible
+                        .iter()
+                        .position(|o| o.identifier == self.selected[..depth])
+                })
+                .unwrap_or(0),
+        };
+        self.select_flattened(visible.get(index))
+    }
+
+    fn select_flattened(&mut self, target: Option<&Flattened>) -> NavigationOutcome {
+        match target {
+            None => {
+                self.select(Vec::new());
+                NavigationOutcome::NothingToSelect
+            }
+            Some(target) if target.identifier == self.selected => NavigationOutcome::AtBoundary,
+            Some(target) => {
+                self.select(target.identifier.clone());
+                NavigationOutcome::Moved
+            }
+        }
+    }
+
+    /// Select the next visible node whose plain text starts with the typed prefix, ignoring case.
+    ///
+    /// The `character` is appended to the prefix typed before unless more than `timeout` passed since the last call.
+    /// A new prefix starts searching after the selected node so typing the same character again cycles through the matches.
+    /// A longer prefix starts at the selected node so it stays selected while it still matches.
+    /// The search wraps around at the end.
+    ///
+    /// Returns [`NothingToSelect`](NavigationOutcome::NothingToSelect) and keeps the selection when no node matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("Cargo.toml"), TreeItem::new_leaf("src")];
+    /// let mut state = TreeState::default();
+    /// state.type_ahead(&items, 's', Instant::now(), Duration::from_secs(1));
+    /// assert_eq!(state.selected(), [1]);
+    /// ```
+    pub fn type_ahead(
+        &mut self,
+        items: &[TreeItem],
+        character: char,
+        now: Instant,
+        timeout: Duration,
+    ) -> NavigationOutcome {
+        let expired = self
+            .type_ahead_time
+            .map_or(true, |last| now.saturating_duration_since(last) > timeout);
+        if expired {
+            self.type_ahead.clear();
+        }
+        self.type_ahead_time = Some(now);
+        self.type_ahead.extend(character.to_lowercase());
+
+        let visible = flatten(&self.get_all_opened(), items);
+        let current = visible.iter().position(|o| o.identifier == self.selected);
+        let start = match current {
+            Some(current) if self.type_ahead.chars().count() == 1 => current + 1,
+            Some(current) => current,
+            None => 0,
+        };
+        let prefix = &self.type_ahead;
+        let found = (0..visible.len())
+            .map(|offset| &visible[(start + offset) % visible.len()])
+            .find(|o| {
+                get_plain_text(&o.item.text)
+                    .to_lowercase()
+                    .starts_with(prefix.as_str())
+            });
+        match found {
+            Some(found) => self.jump_to_flattened(Some(found)),
+            None => NavigationOutcome::NothingToSelect,
+        }
+    }
+
+    /// The prefix typed for [`type_ahead`](Self::type_ahead) in lowercase
+    #[must_use]
+    pub fn type_ahead_prefix(&self) -> &str {
+        &self.type_ahead
+    }
+
+    /// Forget the typed prefix so the next [`type_ahead`](Self::type_ahead) starts a new one.
+    pub fn clear_type_ahead(&mut self) {
+        self.type_ahead.clear();
+        self.type_ahead_time = None;
+    }
+
+    /// Start editing the text of the selected node in place.
+    /// The input starts with the plain text of the node and is rendered instead of it.
+    /// Returns `false` when no existing node is selected.
+    ///
+    /// The edit stays on this node even when the selection moves away.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("alpha"), TreeItem::new_leaf("beta")];
+    /// let mut state = TreeState::default();
+    /// state.select(vec![0]);
+    /// state.start_editing(&items);
+    /// state.editing_mut().unwrap().insert_char('X');
+    /// state.key_down(&items);
+    ///
+    /// assert_eq!(state.commit_editing(), Some((vec![0], "alphaX".to_string())));
+    /// ```
+    pub fn start_editing(&mut self, items: &[TreeItem]) -> bool {
+        match get_item(items, &self.selected) {
+            Some(item) => {
+                let input = TextInput::new(get_plain_text(&item.text));
+                self.editing = Some((self.selected.clone(), input));
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[must_use]
+    pub fn editing(&self) -> Option<&TextInput> {
+        self.editing.as_ref().map(|(_, input)| input)
+    }
+
+    /// Identifier of the node being edited
+    #[must_use]
+    pub fn editing_identifier(&self) -> Option<&[usize]> {
+        self.editing
+            .as_ref()
+            .map(|(identifier, _)| identifier.as_slice())
+    }
+
+    /// The input to forward key presses to while editing
+    pub fn editing_mut(&mut self) -> Option<&mut TextInput> {
+        self.editing.as_mut().map(|(_, input)| input)
+    }
+
+    /// Stop editing and return the edited node with its edited text.
+    /// Returns `None` when not editing.
+    pub fn commit_editing(&mut self) -> Option<(TreeIdentifierVec, String)> {
+        self.edit_cursor_position = None;
+        self.editing
+            .take()
+            .map(|(identifier, input)| (identifier, input.into_value()))
+    }
+
+    /// Stop editing and discard the edited text.
+    /// Returns `false` when not editing.
+    pub fn cancel_editing(&mut self) -> bool {
+        self.edit_cursor_position = None;
+        self.editing.take().is_some()
+    }
+
+    /// Terminal position of the text cursor while editing as rendered by the last [`Tree`] render.
+    /// Can be passed to `Frame::set_cursor` to show the terminal cursor there.
+    #[must_use]
+    pub const fn edit_cursor_position(&self) -> Option<(u16, u16)> {
+        self.edit_cursor_position
+    }
+}
+
+/// Result of a navigation method of [`TreeState`] like [`key_down`](TreeState::key_down)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationOutcome {
+    /// The selection moved to another node
+    Moved,
+    /// The selected node was opened
+    Opened,
+    /// The selected node was closed
+    Closed,
+    /// Nothing changed as the selection is already at the boundary in the requested direction
+    AtBoundary,
+    /// There are no visible nodes or nothing is selected so there is nothing to navigate
+    NothingToSelect,
+}
+
+/// What [`TreeState::reconcile`] changed
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// The selection before and after when it had to be moved
+    pub selection_moved: Option<(TreeIdentifierVec, TreeIdentifierVec)>,
+    /// Opened nodes which were removed as they do not exist anymore or have no children
+    pub removed_opened: Vec<TreeIdentifierVec>,
+    /// Marks which were removed as their node does not exist anymore
+    pub removed_marks: Vec<(char, TreeIdentifierVec)>,
+    /// The offset was beyond the visible nodes and was reduced
+    pub offset_clamped: bool,
+}
+
+impl ReconcileReport {
+    /// Returns `true` when nothing had to be repaired
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        self.selection_moved.is_none()
+            && self.removed_opened.is_empty()
+            && self.removed_marks.is_empty()
+            && !self.offset_clamped
     }
 }
 
@@ -211,7 +1240,7 @@ impl<'a> TreeItem<'a> {
     }
 
     #[must_use]
-    pub fn children(&self) -> &[TreeItem] {
+    pub fn children(&self) -> &[Self] {
         &self.children
     }
 
@@ -239,6 +1268,68 @@ impl<'a> TreeItem<'a> {
     pub fn add_child(&mut self, child: TreeItem<'a>) {
         self.children.push(child);
     }
+
+    /// Insert a child at `index` shifting all children after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > children().len()`.
+    pub fn insert_child(&mut self, index: usize, child: TreeItem<'a>) {
+        self.children.insert(index, child);
+    }
+
+    /// Remove the child at `index` shifting all children after it.
+    /// Returns `None` when there is no child at `index`.
+    pub fn remove_child(&mut self, index: usize) -> Option<Self> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+}
+
+/// Text content without styling, multiple lines are joined by spaces
+#[must_use]
+pub(crate) fn get_plain_text(text: &Text) -> String {
+    text.lines
+        .iter()
+        .map(|line| {
+            line.0
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Get the [`TreeItem`] referenced by the `identifier`
+#[must_use]
+pub(crate) fn get_item<'a, 'b>(
+    items: &'b [TreeItem<'a>],
+    identifier: TreeIdentifier,
+) -> Option<&'b TreeItem<'a>> {
+    let (first, rest) = identifier.split_first()?;
+    let mut current = items.get(*first)?;
+    for index in rest {
+        current = current.children.get(*index)?;
+    }
+    Some(current)
+}
+
+/// Get the children of the node referenced by the `identifier`.
+/// An empty `identifier` references the top level items.
+#[must_use]
+pub(crate) fn get_children_mut<'a, 'b>(
+    items: &'b mut Vec<TreeItem<'a>>,
+    identifier: TreeIdentifier,
+) -> Option<&'b mut Vec<TreeItem<'a>>> {
+    let mut current = items;
+    for index in identifier {
+        current = &mut current.get_mut(*index)?.children;
+    }
+    Some(current)
 }
 
 /// A `Tree` which can be rendered
@@ -288,6 +1379,19 @@ pub struct Tree<'a> {
     node_open_symbol: &'a str,
     /// Symbol displayed in front of a node without children.
     node_no_children_symbol: &'a str,
+
+    /// Style used to render the cursor while editing the selected item
+    edit_cursor_style: Style,
+
+    /// Rows kept visible above and below the selected item when scrolling
+    scroll_off: usize,
+    /// Always scroll the selected item into the middle
+    center_selected: bool,
+
+    /// Show a column with the name of the mark in front of marked items
+    mark_gutter: bool,
+    /// Style used to render the name of a mark in the gutter
+    mark_style: Style,
 }
 
 impl<'a> Tree<'a> {
@@ -306,6 +1410,11 @@ impl<'a> Tree<'a> {
             node_closed_symbol: "\u{25b6} ", // Arrow to right
             node_open_symbol: "\u{25bc} ",   // Arrow down
             node_no_children_symbol: "  ",
+            edit_cursor_style: Style::default().add_modifier(Modifier::REVERSED),
+            scroll_off: 0,
+            center_selected: false,
+            mark_gutter: false,
+            mark_style: Style::default().add_modifier(Modifier::BOLD),
         }
     }
 
@@ -357,6 +1466,99 @@ impl<'a> Tree<'a> {
         self.node_no_children_symbol = symbol;
         self
     }
+
+    #[must_use]
+    pub const fn edit_cursor_style(mut self, style: Style) -> Self {
+        self.edit_cursor_style = style;
+        self
+    }
+
+    /// Keep at least `rows` rows visible above and below the selected item when possible.
+    /// Limited to half of the available height.
+    #[must_use]
+    pub const fn scroll_off(mut self, rows: usize) -> Self {
+        self.scroll_off = rows;
+        self
+    }
+
+    /// Always scroll the selected item into the middle of the available height.
+    /// See [`TreeState::center_on_selected`] to do this only once.
+    #[must_use]
+    pub const fn center_selected(mut self, center_selected: bool) -> Self {
+        self.center_selected = center_selected;
+        self
+    }
+
+    /// Show the name of a mark in front of marked items.
+    /// See [`TreeState::set_mark`].
+    #[must_use]
+    pub const fn mark_gutter(mut self, mark_gutter: bool) -> Self {
+        self.mark_gutter = mark_gutter;
+        self
+    }
+
+    #[must_use]
+    pub const fn mark_style(mut self, style: Style) -> Self {
+        self.mark_style = style;
+        self
+    }
+}
+
+/// Earliest index so that all items from it until `end` (exclusive) fit into `available_height`
+fn get_earliest_start(visible: &[Flattened], end: usize, available_height: usize) -> usize {
+    let mut start = end;
+    let mut height = 0;
+    while let Some(item) = start.checked_sub(1).and_then(|index| visible.get(index)) {
+        if height + item.item.height() > available_height {
+            break;
+        }
+        height += item.item.height();
+        start -= 1;
+    }
+    start
+}
+
+/// Index of the first rendered item so that the selected item is visible.
+///
+/// Keeps `scroll_off` rows of context above and below the selected item when possible
+/// and otherwise changes the `offset` as little as possible.
+/// When `center` is set the selected item is placed in the middle instead.
+fn get_start_showing_selected(
+    visible: &[Flattened],
+    selected: usize,
+    offset: usize,
+    available_height: usize,
+    scroll_off: usize,
+    center: bool,
+) -> usize {
+    let free = available_height.saturating_sub(visible[selected].item.height());
+    let margin = if center {
+        free / 2
+    } else {
+        scroll_off.min(free / 2)
+    };
+
+    // Latest start which still shows `margin` rows above the selected item
+    let mut latest = selected;
+    let mut above = 0;
+    while latest > 0 && above < margin {
+        latest -= 1;
+        above += visible[latest].item.height();
+    }
+    if center {
+        return latest;
+    }
+
+    // Earliest start which still shows `margin` rows below the selected item
+    let mut end = selected + 1;
+    let mut below = 0;
+    while end < visible.len() && below < margin {
+        below += visible[end].item.height();
+        end += 1;
+    }
+    let earliest = get_earliest_start(visible, end, available_height);
+
+    offset.min(latest).max(earliest).min(selected)
 }
 
 impl<'a> StatefulWidget for Tree<'a> {
@@ -365,6 +1567,8 @@ impl<'a> StatefulWidget for Tree<'a> {
     #[allow(clippy::too_many_lines)]
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         buf.set_style(area, self.style);
+        state.edit_cursor_position = None;
+        state.rendered.clear();
 
         // Get the inner area inside a possible block, otherwise use the full area
         let area = self.block.map_or(area, |b| {
@@ -373,6 +1577,7 @@ impl<'a> StatefulWidget for Tree<'a> {
             inner_area
         });
 
+        state.viewport_height = area.height as usize;
         if area.width < 1 || area.height < 1 {
             return;
         }
@@ -392,7 +1597,22 @@ impl<'a> StatefulWidget for Tree<'a> {
                 .unwrap_or(0)
         };
 
-        let mut start = state.offset.min(selected_index);
+        let center = self.center_selected || state.center_once;
+        state.center_once = false;
+        let start = if state.detached {
+            // Scroll at most until the last item is at the bottom
+            let max_start = get_earliest_start(&visible, visible.len(), available_height);
+            state.offset.min(max_start.min(visible.len() - 1))
+        } else {
+            get_start_showing_selected(
+                &visible,
+                selected_index,
+                state.offset,
+                available_height,
+                self.scroll_off,
+                center,
+            )
+        };
         let mut end = start;
         let mut height = 0;
         for item in visible.iter().skip(start) {
@@ -404,15 +1624,6 @@ impl<'a> StatefulWidget for Tree<'a> {
             end += 1;
         }
 
-        while selected_index >= end {
-            height = height.saturating_add(visible[end].item.height());
-            end += 1;
-            while height > available_height {
-                height = height.saturating_sub(visible[start].item.height());
-                start += 1;
-            }
-        }
-
         state.offset = start;
 
         let blank_symbol = " ".repeat(self.highlight_symbol.width());
@@ -440,9 +1651,44 @@ impl<'a> StatefulWidget for Tree<'a> {
                 height: item.item.height() as u16,
             };
 
+            state.rendered.push(RenderedNode {
+                area,
+                identifier: item.identifier.clone(),
+                has_children: !item.item.children.is_empty(),
+            });
+
             let item_style = self.style.patch(item.item.style);
             buf.set_style(area, item_style);
 
+            // Width left in the row after drawing until `after_x`
+            let remaining_width = |after_x: u16| area.width.saturating_sub(after_x - x);
+
+            let after_gutter_x = if self.mark_gutter {
+                let mark = state
+                    .marks
+                    .iter()
+                    .find(|(_, identifier)| **identifier == item.identifier)
+                    .map_or(' ', |(name, _)| *name);
+                let after_mark_x = set_stringn_within(
+                    buf,
+                    x,
+                    y,
+                    &mark.to_string(),
+                    remaining_width(x),
+                    item_style.patch(self.mark_style),
+                );
+                set_stringn_within(
+                    buf,
+                    after_mark_x,
+                    y,
+                    " ",
+                    remaining_width(after_mark_x),
+                    item_style,
+                )
+            } else {
+                x
+            };
+
             let is_selected = state.selected == item.identifier;
             let after_highlight_symbol_x = if has_selection {
                 let symbol = if is_selected {
@@ -450,19 +1696,26 @@ impl<'a> StatefulWidget for Tree<'a> {
                 } else {
                     &blank_symbol
                 };
-                let (x, _) = buf.set_stringn(x, y, symbol, area.width as usize, item_style);
-                x
+                set_stringn_within(
+                    buf,
+                    after_gutter_x,
+                    y,
+                    symbol,
+                    remaining_width(after_gutter_x),
+                    item_style,
+                )
             } else {
-                x
+                after_gutter_x
             };
 
             let after_depth_x = {
                 let indent_width = item.depth() * 2;
-                let (after_indent_x, _) = buf.set_stringn(
+                let after_indent_x = set_stringn_within(
+                    buf,
                     after_highlight_symbol_x,
                     y,
-                    " ".repeat(indent_width),
-                    indent_width,
+                    &" ".repeat(indent_width),
+                    remaining_width(after_highlight_symbol_x).min(indent_width as u16),
                     item_style,
                 );
                 let symbol = if item.item.children.is_empty() {
@@ -472,26 +1725,855 @@ impl<'a> StatefulWidget for Tree<'a> {
                 } else {
                     self.node_closed_symbol
                 };
-                let max_width = area.width.saturating_sub(after_indent_x - x);
-                let (x, _) =
-                    buf.set_stringn(after_indent_x, y, symbol, max_width as usize, item_style);
-                x
+                set_stringn_within(
+                    buf,
+                    after_indent_x,
+                    y,
+                    symbol,
+                    remaining_width(after_indent_x),
+                    item_style,
+                )
             };
 
-            let max_element_width = area.width.saturating_sub(after_depth_x - x);
-            for (j, line) in item.item.text.lines.iter().enumerate() {
-                buf.set_spans(after_depth_x, y + j as u16, line, max_element_width);
+            let max_element_width = remaining_width(after_depth_x);
+            let editing = state
+                .editing
+                .as_ref()
+                .filter(|(identifier, _)| *identifier == item.identifier)
+                .map(|(_, input)| input);
+            if let Some(input) = editing {
+                let (visible, cursor_column) = input.visible(max_element_width as usize);
+                if max_element_width > 0 {
+                    buf.set_stringn(
+                        after_depth_x,
+                        y,
+                        visible,
+                        max_element_width as usize,
+                        item_style,
+                    );
+                }
+                if (cursor_column as u16) < max_element_width {
+                    state.edit_cursor_position = Some((after_depth_x + cursor_column as u16, y));
+                }
+            } else {
+                for (j, line) in item.item.text.lines.iter().enumerate() {
+                    buf.set_spans(after_depth_x, y + j as u16, line, max_element_width);
+                }
             }
             if is_selected {
                 buf.set_style(area, self.highlight_style);
             }
+            if let (Some(_), Some((x, y))) = (editing, state.edit_cursor_position) {
+                buf.set_style(Rect::new(x, y, 1, 1), self.edit_cursor_style);
+            }
         }
     }
 }
 
+/// Like [`Buffer::set_stringn`] but draws nothing when no `width` is left
+/// as `set_stringn` accesses the cell at `x` even then, which might be outside of the buffer.
+/// Returns the x after the drawn string.
+fn set_stringn_within(
+    buf: &mut Buffer,
+    x: u16,
+    y: u16,
+    string: &str,
+    width: u16,
+    style: Style,
+) -> u16 {
+    if width == 0 {
+        return x;
+    }
+    let (x, _) = buf.set_stringn(x, y, string, width as usize, style);
+    x
+}
+
 impl<'a> Widget for Tree<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let mut state = TreeState::default();
         StatefulWidget::render(self, area, buf, &mut state);
     }
 }
+
+#[test]
+fn validate_drops_missing() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    assert!(!state.validate(&items));
+
+    state.open(vec![1, 7]);
+    state.select(vec![3]);
+    assert!(state.validate(&items));
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 1]]);
+    assert!(state.selected().is_empty());
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+fn serde_roundtrip() {
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![1, 2]);
+    state.set_mark('a');
+    state.start_editing(&crate::flatten::get_example_tree_items());
+
+    let json = serde_json::to_string(&state).unwrap();
+    let loaded: TreeState = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.get_all_opened(), [vec![1]]);
+    assert_eq!(loaded.selected(), [1, 2]);
+    assert_eq!(loaded.marks(), [('a', vec![1, 2])]);
+    assert!(loaded.editing().is_none());
+
+    let partial: TreeState = serde_json::from_str(r#"{"selected":[2]}"#).unwrap();
+    assert_eq!(partial.selected(), [2]);
+}
+
+#[test]
+fn reconcile_moves_selection_to_sibling_or_parent() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![1, 1, 5]);
+    let report = state.reconcile(&items);
+    assert_eq!(report.selection_moved, Some((vec![1, 1, 5], vec![1, 1, 1])));
+
+    state.select(vec![0, 2]);
+    state.reconcile(&items);
+    assert_eq!(state.selected(), [0]);
+
+    state.select(vec![7, 2]);
+    state.reconcile(&items);
+    assert_eq!(state.selected(), [2]);
+
+    state.select(vec![1]);
+    assert!(state.reconcile(&items).is_unchanged());
+
+    state.select(vec![1]);
+    state.reconcile(&[]);
+    assert!(state.selected().is_empty());
+}
+
+#[test]
+fn reconcile_prunes_opened_and_offset() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![0]);
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.open(vec![4]);
+    state.marks.insert('a', vec![0]);
+    state.marks.insert('x', vec![1, 5]);
+    state.offset = 20;
+    let report = state.reconcile(&items);
+    assert_eq!(report.removed_opened, [vec![0], vec![4]]);
+    assert_eq!(report.removed_marks, [('x', vec![1, 5])]);
+    assert_eq!(state.marks(), [('a', vec![0])]);
+    assert!(report.offset_clamped);
+    assert_eq!(state.get_offset(), 7);
+}
+
+#[test]
+fn reconcile_and_validate_drop_missing_jumps_and_editing() {
+    let items = crate::flatten::get_example_tree_items();
+    let changed = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("h")];
+    for reconcile in [true, false] {
+        let mut state = TreeState::default();
+        state.select_and_reveal(&items, &[1, 1, 0]);
+        assert!(state.start_editing(&items));
+        state.select_last(&items);
+
+        if reconcile {
+            state.reconcile(&changed);
+        } else {
+            assert!(state.validate(&changed));
+        }
+        assert!(state.editing().is_none());
+        assert_eq!(state.jump_back(), NavigationOutcome::AtBoundary);
+        assert!(!state.get_all_opened().contains(&vec![1, 1]));
+    }
+}
+
+#[test]
+fn close_moves_selection_to_closed_ancestor() {
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 0]);
+    assert!(state.close(&[1]));
+    assert_eq!(state.selected(), [1]);
+
+    state.open(vec![1]);
+    state.select(vec![1, 1, 0]);
+    state.toggle(vec![1, 1]);
+    assert_eq!(state.selected(), [1, 1]);
+
+    state.select(vec![2]);
+    state.toggle(vec![1]);
+    assert_eq!(state.selected(), [2]);
+}
+
+#[test]
+fn close_all_selects_top_level() {
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 0]);
+    state.close_all();
+    assert_eq!(state.selected(), [1]);
+    assert!(state.get_all_opened().is_empty());
+}
+
+#[test]
+fn navigation_on_empty_items() {
+    let mut state = TreeState::default();
+    assert_eq!(state.key_down(&[]), NavigationOutcome::NothingToSelect);
+    assert_eq!(state.key_up(&[]), NavigationOutcome::NothingToSelect);
+    assert_eq!(state.select_first(&[]), NavigationOutcome::NothingToSelect);
+    assert_eq!(state.select_last(&[]), NavigationOutcome::NothingToSelect);
+    assert_eq!(state.key_left(&[]), NavigationOutcome::NothingToSelect);
+    assert_eq!(state.key_right(&[]), NavigationOutcome::NothingToSelect);
+
+    state.select(vec![3]);
+    assert_eq!(state.key_down(&[]), NavigationOutcome::NothingToSelect);
+    assert!(state.selected().is_empty());
+}
+
+#[test]
+fn navigation_reports_boundaries() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.key_up(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0]);
+    assert_eq!(state.key_up(&items), NavigationOutcome::AtBoundary);
+    assert_eq!(state.select_first(&items), NavigationOutcome::AtBoundary);
+    assert_eq!(state.key_left(&items), NavigationOutcome::AtBoundary);
+    assert_eq!(state.key_right(&items), NavigationOutcome::AtBoundary);
+    assert!(state.get_all_opened().is_empty());
+    assert_eq!(state.selected(), [0]);
+
+    assert_eq!(state.select_last(&items), NavigationOutcome::Moved);
+    assert_eq!(state.key_down(&items), NavigationOutcome::AtBoundary);
+    assert_eq!(state.select_last(&items), NavigationOutcome::AtBoundary);
+
+    assert_eq!(state.key_up(&items), NavigationOutcome::Moved);
+    assert_eq!(state.key_right(&items), NavigationOutcome::Opened);
+    assert_eq!(state.key_right(&items), NavigationOutcome::AtBoundary);
+    assert_eq!(state.key_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 0]);
+    assert_eq!(state.key_left(&items), NavigationOutcome::Moved);
+    assert_eq!(state.key_left(&items), NavigationOutcome::Closed);
+}
+
+#[test]
+fn navigation_recovers_from_stale_selection() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![1, 1, 0]);
+    assert_eq!(state.key_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1]);
+
+    state.select(vec![9]);
+    assert_eq!(state.key_up(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0]);
+
+    state.select(vec![9]);
+    assert_eq!(state.key_right(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0]);
+    assert!(state.get_all_opened().is_empty());
+
+    state.select(vec![1, 5]);
+    assert_eq!(state.key_left(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1]);
+    assert!(state.get_all_opened().is_empty());
+}
+
+#[test]
+fn key_right_on_leaf() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![1, 0]);
+    assert_eq!(state.key_right(&items), NavigationOutcome::AtBoundary);
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+    assert_eq!(state.selected(), [1, 0]);
+}
+
+#[test]
+fn sibling_navigation() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![0]);
+    assert_eq!(
+        state.select_previous_sibling(&items),
+        NavigationOutcome::AtBoundary
+    );
+    assert_eq!(state.select_next_sibling(&items), NavigationOutcome::Moved);
+    assert_eq!(state.select_next_sibling(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [2]);
+    assert_eq!(
+        state.select_next_sibling(&items),
+        NavigationOutcome::AtBoundary
+    );
+    assert_eq!(state.select_first_sibling(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0]);
+    assert_eq!(state.select_last_sibling(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [2]);
+
+    state.select(vec![5]);
+    assert_eq!(
+        state.select_next_sibling(&items),
+        NavigationOutcome::NothingToSelect
+    );
+}
+
+#[test]
+fn parent_and_child_navigation() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.select_parent(), NavigationOutcome::NothingToSelect);
+    state.select(vec![1]);
+    assert_eq!(state.select_parent(), NavigationOutcome::AtBoundary);
+
+    assert_eq!(state.select_last_child(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 2]);
+    assert_eq!(
+        state.select_last_child(&items),
+        NavigationOutcome::AtBoundary
+    );
+    assert_eq!(
+        state.select_previous_sibling(&items),
+        NavigationOutcome::Moved
+    );
+    assert_eq!(state.select_first_child(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 1, 0]);
+    assert_eq!(state.get_all_opened().len(), 2);
+
+    assert_eq!(
+        state.select_parent_next_sibling(&items),
+        NavigationOutcome::Moved
+    );
+    assert_eq!(state.selected(), [1, 2]);
+    assert_eq!(
+        state.select_parent_next_sibling(&items),
+        NavigationOutcome::Moved
+    );
+    assert_eq!(state.selected(), [2]);
+    assert_eq!(
+        state.select_parent_next_sibling(&items),
+        NavigationOutcome::AtBoundary
+    );
+}
+
+#[cfg(test)]
+fn render_example(state: &mut TreeState, items: &[TreeItem], height: u16) {
+    render_example_tree(state, Tree::new(items.to_vec()), height);
+}
+
+#[cfg(test)]
+fn render_example_tree(state: &mut TreeState, tree: Tree, height: u16) {
+    let area = Rect::new(0, 0, 10, height);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(tree, area, &mut buffer, state);
+}
+
+#[test]
+fn page_navigation_uses_viewport_height() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![0]);
+    render_example(&mut state, &items, 3);
+    assert_eq!(state.viewport_height(), 3);
+
+    assert_eq!(state.page_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 1]);
+    assert_eq!(state.get_offset(), 3);
+    assert_eq!(state.half_page_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 1, 0]);
+    assert_eq!(state.page_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [2]);
+    assert_eq!(state.get_offset(), 5);
+    assert_eq!(state.page_down(&items), NavigationOutcome::AtBoundary);
+
+    let area = Rect::new(0, 0, 10, 3);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(Tree::new(items.clone()), area, &mut buffer, &mut state);
+    assert_eq!(state.get_offset(), 5);
+    let last_row = buffer.content[20..30]
+        .iter()
+        .map(|cell| cell.symbol.as_str())
+        .collect::<String>();
+    assert_eq!(last_row.trim(), "h");
+
+    assert_eq!(state.page_up(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 1, 0]);
+    assert_eq!(state.half_page_up(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 1]);
+}
+
+#[test]
+fn page_navigation_respects_item_height() {
+    let items = vec![
+        TreeItem::new_leaf("a"),
+        TreeItem::new_leaf("b\nb"),
+        TreeItem::new_leaf("c\nc"),
+        TreeItem::new_leaf("d"),
+    ];
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    render_example(&mut state, &items, 4);
+    assert_eq!(state.page_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [2]);
+    assert_eq!(state.page_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [3]);
+}
+
+#[test]
+fn scrolling_keeps_selection() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![0]);
+    render_example(&mut state, &items, 3);
+
+    assert!(state.scroll_down(&items, 2));
+    assert!(state.is_detached());
+    render_example(&mut state, &items, 3);
+    assert_eq!(state.get_offset(), 2);
+    assert_eq!(state.selected(), [0]);
+
+    assert!(state.scroll_down(&items, 20));
+    assert_eq!(state.get_offset(), 5);
+    assert!(!state.scroll_down(&items, 1));
+    render_example(&mut state, &items, 3);
+    assert_eq!(state.get_offset(), 5);
+
+    assert!(state.scroll_up(4));
+    render_example(&mut state, &items, 3);
+    assert_eq!(state.get_offset(), 1);
+    assert!(state.scroll_up(4));
+    assert!(!state.scroll_up(1));
+
+    state.scroll_down(&items, 3);
+    state.key_down(&items);
+    assert!(!state.is_detached());
+    render_example(&mut state, &items, 3);
+    assert_eq!(state.get_offset(), 1);
+}
+
+#[cfg(test)]
+fn get_many_items() -> Vec<TreeItem<'static>> {
+    (0..20)
+        .map(|index| TreeItem::new_leaf(index.to_string()))
+        .collect()
+}
+
+#[test]
+fn scroll_off_keeps_context() {
+    let items = get_many_items();
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    for _ in 0..6 {
+        state.key_down(&items);
+        render_example_tree(&mut state, Tree::new(items.clone()).scroll_off(2), 5);
+    }
+    assert_eq!(state.selected(), [6]);
+    assert_eq!(state.get_offset(), 4);
+
+    for _ in 0..3 {
+        state.key_up(&items);
+        render_example_tree(&mut state, Tree::new(items.clone()).scroll_off(2), 5);
+    }
+    assert_eq!(state.selected(), [3]);
+    assert_eq!(state.get_offset(), 1);
+
+    state.select_last(&items);
+    render_example_tree(&mut state, Tree::new(items.clone()).scroll_off(2), 5);
+    assert_eq!(state.get_offset(), 15);
+}
+
+#[test]
+fn center_selected() {
+    let items = get_many_items();
+    let mut state = TreeState::default();
+    state.select(vec![10]);
+    render_example_tree(
+        &mut state,
+        Tree::new(items.clone()).center_selected(true),
+        5,
+    );
+    assert_eq!(state.get_offset(), 8);
+
+    state.select(vec![1]);
+    render_example(&mut state, &items, 5);
+    assert_eq!(state.get_offset(), 1);
+    state.select(vec![12]);
+    state.center_on_selected();
+    render_example(&mut state, &items, 5);
+    assert_eq!(state.get_offset(), 10);
+    state.key_down(&items);
+    render_example(&mut state, &items, 5);
+    assert_eq!(state.get_offset(), 10);
+}
+
+#[cfg(test)]
+fn get_sorted_opened(state: &TreeState) -> Vec<TreeIdentifierVec> {
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    opened
+}
+
+#[test]
+fn open_all_skips_leaves() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open_all(&items);
+    assert_eq!(get_sorted_opened(&state), [vec![1], vec![1, 1]]);
+}
+
+#[test]
+fn open_depth() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open_all(&items);
+    state.select(vec![1, 1, 0]);
+    state.set_open_depth(&items, 1);
+    assert_eq!(get_sorted_opened(&state), [vec![1]]);
+    assert_eq!(state.selected(), [1, 1]);
+    state.set_open_depth(&items, 2);
+    assert_eq!(get_sorted_opened(&state), [vec![1], vec![1, 1]]);
+    state.set_open_depth(&items, 0);
+    assert!(state.get_all_opened().is_empty());
+    assert_eq!(state.selected(), [1]);
+}
+
+#[test]
+fn recursive_open_and_close() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![1]);
+    state.open_selected_recursive(&items);
+    assert_eq!(get_sorted_opened(&state), [vec![1], vec![1, 1]]);
+
+    state.select(vec![1, 1]);
+    state.close_selected_recursive();
+    assert_eq!(get_sorted_opened(&state), [vec![1]]);
+
+    state.open_all(&items);
+    state.select(vec![1, 1, 0]);
+    state.close_all_except_selected();
+    assert_eq!(get_sorted_opened(&state), [vec![1], vec![1, 1]]);
+    state.select(vec![1, 1]);
+    state.close_all_except_selected();
+    assert_eq!(get_sorted_opened(&state), [vec![1]]);
+}
+
+#[test]
+fn select_and_reveal_scrolls_into_view() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    render_example(&mut state, &items, 2);
+
+    assert!(!state.select_and_reveal(&items, &[1, 3]));
+    assert_eq!(state.selected(), [0]);
+
+    assert!(state.select_and_reveal(&items, &[1, 1, 1]));
+    assert_eq!(get_sorted_opened(&state), [vec![1], vec![1, 1]]);
+    render_example(&mut state, &items, 2);
+    assert_eq!(state.get_offset(), 4);
+}
+
+#[test]
+fn accordion_closes_siblings() {
+    let items = vec![
+        TreeItem::new("a", vec![TreeItem::new("b", vec![TreeItem::new_leaf("c")])]),
+        TreeItem::new("d", vec![TreeItem::new_leaf("e")]),
+    ];
+    let mut state = TreeState::default();
+    state.set_accordion(true);
+    state.open(vec![0]);
+    state.open(vec![0, 0]);
+    state.select(vec![0, 0, 0]);
+
+    state.open(vec![1]);
+    assert_eq!(get_sorted_opened(&state), [vec![1]]);
+    assert_eq!(state.selected(), [0]);
+
+    state.reveal(&items, &[0, 0, 0]);
+    assert_eq!(get_sorted_opened(&state), [vec![0], vec![0, 0]]);
+
+    state.select(vec![0, 0, 0]);
+    assert!(state.select_and_reveal(&items, &[1, 0]));
+    assert_eq!(get_sorted_opened(&state), [vec![1]]);
+    assert_eq!(state.jump_back(), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0, 0, 0]);
+}
+
+#[test]
+fn type_ahead_cycles_and_extends() {
+    let items = vec![
+        TreeItem::new_leaf("Alpha"),
+        TreeItem::new("beta", vec![TreeItem::new_leaf("alpine")]),
+        TreeItem::new_leaf("Alps"),
+    ];
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    let timeout = Duration::from_secs(1);
+    let start = Instant::now();
+
+    state.type_ahead(&items, 'a', start, timeout);
+    assert_eq!(state.selected(), [0]);
+    state.type_ahead(&items, 'L', start, timeout);
+    state.type_ahead(&items, 'p', start, timeout);
+    assert_eq!(state.type_ahead_prefix(), "alp");
+    assert_eq!(state.selected(), [0]);
+    state.type_ahead(&items, 's', start, timeout);
+    assert_eq!(state.selected(), [2]);
+
+    let later = start + Duration::from_secs(2);
+    assert_eq!(
+        state.type_ahead(&items, 'a', later, timeout),
+        NavigationOutcome::Moved
+    );
+    assert_eq!(state.selected(), [0]);
+    state.type_ahead(&items, 'a', later + Duration::from_secs(2), timeout);
+    assert_eq!(state.selected(), [1, 0]);
+
+    state.clear_type_ahead();
+    assert_eq!(
+        state.type_ahead(&items, 'x', later, timeout),
+        NavigationOutcome::NothingToSelect
+    );
+    assert_eq!(state.selected(), [1, 0]);
+}
+
+#[test]
+fn click_selects_and_toggles() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    render_example(&mut state, &items, 4);
+    assert_eq!(state.identifier_at(0, 1), Some(&[1][..]));
+    assert_eq!(state.identifier_at(0, 4), None);
+
+    assert_eq!(state.click_at(3, 1), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1]);
+    assert_eq!(state.click_at(3, 1), NavigationOutcome::Opened);
+    render_example(&mut state, &items, 4);
+    assert_eq!(state.identifier_at(0, 3), Some(&[1, 1][..]));
+    assert_eq!(state.click_at(3, 1), NavigationOutcome::Closed);
+    assert_eq!(state.click_at(0, 10), NavigationOutcome::NothingToSelect);
+}
+
+#[test]
+fn jump_list_restores_position() {
+    let items = get_many_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.jump_back(), NavigationOutcome::AtBoundary);
+    state.select(vec![3]);
+    render_example(&mut state, &items, 5);
+    state.select_last(&items);
+    render_example(&mut state, &items, 5);
+    assert_eq!(state.get_offset(), 15);
+    state.key_up(&items);
+    state.select_first(&items);
+
+    assert_eq!(state.jump_back(), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [18]);
+    assert_eq!(state.jump_back(), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [3]);
+    assert_eq!(state.get_offset(), 0);
+    assert_eq!(state.jump_back(), NavigationOutcome::AtBoundary);
+
+    assert_eq!(state.jump_forward(), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [18]);
+    assert_eq!(state.get_offset(), 15);
+    assert_eq!(state.jump_forward(), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0]);
+    assert_eq!(state.jump_forward(), NavigationOutcome::AtBoundary);
+
+    // A new jump forgets the positions after the current one
+    state.jump_back();
+    state.select_and_reveal(&items, &[10]);
+    assert_eq!(state.jump_forward(), NavigationOutcome::AtBoundary);
+    assert_eq!(state.jump_back(), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [18]);
+}
+
+#[test]
+fn jump_list_is_bounded() {
+    let items = get_many_items();
+    let mut state = TreeState::default();
+    for _ in 0..MAX_JUMPS {
+        state.select_first(&items);
+        state.select_last(&items);
+    }
+    assert_eq!(state.jumps.len(), MAX_JUMPS);
+}
+
+#[test]
+fn marks_jump_and_render_gutter() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert!(!state.set_mark('a'));
+    state.select_and_reveal(&items, &[1, 1, 0]);
+    assert!(state.set_mark('a'));
+    state.select(vec![2]);
+    state.set_mark('b');
+    state.close_all();
+    state.select(vec![0]);
+    assert_eq!(state.marks(), [('a', vec![1, 1, 0]), ('b', vec![2])]);
+
+    assert_eq!(state.jump_to_mark(&items, 'a'), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [1, 1, 0]);
+    assert_eq!(
+        state.jump_to_mark(&items, 'c'),
+        NavigationOutcome::NothingToSelect
+    );
+    assert_eq!(state.jump_back(), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0]);
+
+    let area = Rect::new(0, 0, 10, 3);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items.clone()).mark_gutter(true);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let rows = buffer
+        .content
+        .chunks(10)
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.symbol.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(rows[0], "    a     ");
+
+    assert!(state.clear_mark('b'));
+    assert!(!state.clear_mark('b'));
+    state.select(vec![2]);
+    state.set_mark('b');
+    state.close_all();
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items.clone()).mark_gutter(true);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    assert_eq!(buffer.content[20].symbol, "b");
+
+    state.clear_marks();
+    assert!(state.marks().is_empty());
+}
+
+#[test]
+fn mark_gutter_stays_inside_the_area() {
+    use tui::widgets::Borders;
+    let items = vec![TreeItem::new_leaf("abcdefghijklmnop")];
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    state.set_mark('a');
+
+    let area = Rect::new(0, 0, 12, 3);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items.clone())
+        .block(Block::default().borders(Borders::ALL))
+        .mark_gutter(true);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let row = buffer.content[12..24]
+        .iter()
+        .map(|cell| cell.symbol.as_str())
+        .collect::<String>();
+    assert_eq!(row, "\u{2502}a   abcdef\u{2502}");
+
+    for width in 1..=3 {
+        let buffer_area = Rect::new(0, 0, 5, 1);
+        let mut buffer = Buffer::empty(buffer_area);
+        let area = Rect::new(5 - width, 0, width, 1);
+        let tree = Tree::new(items.clone()).mark_gutter(true);
+        StatefulWidget::render(tree, area, &mut buffer, &mut state);
+        assert_eq!(buffer.content[4 - width as usize].symbol, " ");
+        assert_eq!(buffer.content[5 - width as usize].symbol, "a");
+    }
+}
+
+#[test]
+fn wrap_around_key_up_and_down() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![2]);
+    assert_eq!(state.key_down(&items), NavigationOutcome::AtBoundary);
+
+    state.set_wrap_around(true);
+    assert_eq!(state.key_down(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [0]);
+    assert_eq!(state.key_up(&items), NavigationOutcome::Moved);
+    assert_eq!(state.selected(), [2]);
+
+    let single = vec![TreeItem::new_leaf("a")];
+    state.select(vec![0]);
+    assert_eq!(state.key_down(&single), NavigationOutcome::AtBoundary);
+}
+
+#[test]
+fn editing_renders_on_edited_row() {
+    let items = vec![TreeItem::new_leaf("alpha"), TreeItem::new_leaf("beta")];
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    assert!(state.start_editing(&items));
+    state.editing_mut().unwrap().insert_char('X');
+    state.key_down(&items);
+
+    let area = Rect::new(0, 0, 12, 2);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(Tree::new(items.clone()), area, &mut buffer, &mut state);
+    let rows = buffer
+        .content
+        .chunks(12)
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.symbol.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(rows, ["  alphaX    ", "  beta      "]);
+    assert_eq!(state.edit_cursor_position(), Some((8, 0)));
+
+    assert_eq!(state.editing_identifier(), Some(&[0][..]));
+    assert_eq!(
+        state.commit_editing(),
+        Some((vec![0], "alphaX".to_string()))
+    );
+    assert_eq!(state.selected(), [1]);
+    assert_eq!(state.edit_cursor_position(), None);
+}
+
+#[test]
+fn editing_in_narrow_area() {
+    let items = vec![TreeItem::new_leaf("abc")];
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    state.start_editing(&items);
+    for width in 1..=2 {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+        let area = Rect::new(4 - width, 0, width, 1);
+        StatefulWidget::render(Tree::new(items.clone()), area, &mut buffer, &mut state);
+        assert_eq!(state.edit_cursor_position(), None);
+    }
+}
+
+#[test]
+fn editing_cursor_follows_input() {
+    let items = vec![TreeItem::new_leaf("abc")];
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    state.start_editing(&items);
+    state.editing_mut().unwrap().move_cursor_home();
+    render_example(&mut state, &items, 1);
+    assert_eq!(state.edit_cursor_position(), Some((2, 0)));
+    assert!(state.cancel_editing());
+    render_example(&mut state, &items, 1);
+    assert_eq!(state.edit_cursor_position(), None);
+}
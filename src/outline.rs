@@ -0,0 +1,432 @@
+use std::fmt;
+
+use crate::TreeItem;
+
+/// How one level of depth is indented in the text given to [`parse_indented`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indentation {
+    /// One tab per level
+    Tabs,
+    /// The given amount of spaces per level
+    Spaces(usize),
+    /// Use the indentation of the first indented line
+    Detect,
+    /// Output of the `tree` command like `├── src` or `│   └── lib.rs`.
+    /// Its summary line like `2 directories, 3 files` is skipped.
+    TreeCommand,
+}
+
+/// What went wrong while parsing an outline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineErrorKind {
+    /// Tabs and spaces are mixed in the indentation
+    MixedIndentation,
+    /// The indentation is not a multiple of the spaces per level
+    UnevenIndentation { spaces: usize, per_level: usize },
+    /// The line is indented more than one level deeper than the line before
+    SkippedLevel { depth: usize, max_depth: usize },
+    /// The indentation of a Markdown list item does not match the list items before
+    UnalignedListItem { indentation: usize },
+}
+
+/// Error returned by [`parse_indented`] and [`parse_markdown`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutlineError {
+    /// Line number starting with 1
+    pub line: usize,
+    pub kind: OutlineErrorKind,
+}
+
+impl fmt::Display for OutlineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match self.kind {
+            OutlineErrorKind::MixedIndentation => {
+                write!(f, "indentation mixes tabs and spaces")
+            }
+            OutlineErrorKind::UnevenIndentation { spaces, per_level } => write!(
+                f,
+                "indentation of {} spaces is not a multiple of {}",
+                spaces, per_level
+            ),
+            OutlineErrorKind::SkippedLevel { depth, max_depth } => write!(
+                f,
+                "indentation depth {} is deeper than the allowed {}",
+                depth, max_depth
+            ),
+            OutlineErrorKind::UnalignedListItem { indentation } => write!(
+                f,
+                "list item indentation of {} does not match the list items before",
+                indentation
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OutlineError {}
+
+/// Parse text where the depth of each line is given by its indentation
+///
+/// Blank lines are skipped.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{parse_indented, Indentation};
+/// let items = parse_indented("a\n  b\n  c\nd", Indentation::Spaces(2)).unwrap();
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items[0].children().len(), 2);
+/// ```
+///
+/// # Errors
+///
+/// Errors when the indentation is inconsistent like mixing tabs and spaces or skipping levels.
+pub fn parse_indented(
+    text: &str,
+    indentation: Indentation,
+) -> Result<Vec<TreeItem<'_>>, OutlineError> {
+    let mut indentation = indentation;
+    let mut nodes = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = |kind| OutlineError {
+            line: index + 1,
+            kind,
+        };
+
+        let whitespace = line.len() - line.trim_start().len();
+        let (prefix, content) = line.split_at(whitespace);
+        let has_tabs = prefix.contains('\t');
+        let has_spaces = prefix.contains(' ');
+
+        if indentation == Indentation::Detect && !prefix.is_empty() {
+            indentation = if has_tabs {
+                Indentation::Tabs
+            } else {
+                Indentation::Spaces(prefix.len())
+            };
+        }
+
+        let (depth, content) = match indentation {
+            Indentation::Detect => (0, content),
+            Indentation::Tabs => {
+                if has_spaces {
+                    return Err(error(OutlineErrorKind::MixedIndentation));
+                }
+                (prefix.len(), content)
+            }
+            Indentation::Spaces(per_level) => {
+                if has_tabs {
+                    return Err(error(OutlineErrorKind::MixedIndentation));
+                }
+                let spaces = prefix.len();
+                if per_level == 0 || spaces % per_level != 0 {
+                    return Err(error(OutlineErrorKind::UnevenIndentation {
+                        spaces,
+                        per_level,
+                    }));
+                }
+                (spaces / per_level, content)
+            }
+            Indentation::TreeCommand => match parse_tree_command_line(line) {
+                (0, content) if is_tree_command_summary(content) => continue,
+                parsed => parsed,
+            },
+        };
+
+        let max_depth = nodes.last().map_or(0, |(depth, _)| depth + 1);
+        if depth > max_depth {
+            return Err(error(OutlineErrorKind::SkippedLevel { depth, max_depth }));
+        }
+        nodes.push((depth, content.trim_end()));
+    }
+    Ok(build(nodes))
+}
+
+/// Depth and content of a line from the output of the `tree` command
+fn parse_tree_command_line(line: &str) -> (usize, &str) {
+    let mut depth = 0;
+    let mut rest = line;
+    loop {
+        let units = ["\u{2502}   ", "    ", "|   ", "\u{2502}\u{a0}\u{a0} "];
+        if let Some(stripped) = units.iter().find_map(|unit| rest.strip_prefix(unit)) {
+            depth += 1;
+            rest = stripped;
+            continue;
+        }
+        let branches = [
+            "\u{251c}\u{2500}\u{2500} ",
+            "\u{2514}\u{2500}\u{2500} ",
+            "|-- ",
+            "`-- ",
+        ];
+        if let Some(stripped) = branches.iter().find_map(|unit| rest.strip_prefix(unit)) {
+            return (depth + 1, stripped);
+        }
+        return (depth, rest.trim_start());
+    }
+}
+
+/// Check for the summary line of the `tree` command like `1 directory, 2 files` or `3 directories`
+fn is_tree_command_summary(content: &str) -> bool {
+    content.trim_end().split(", ").all(|part| {
+        let mut words = part.split(' ');
+        let count = words.next().unwrap_or_default();
+        let noun = words.next().unwrap_or_default();
+        !count.is_empty()
+            && count.chars().all(|c| c.is_ascii_digit())
+            && matches!(noun, "directory" | "directories" | "file" | "files")
+            && words.next().is_none()
+    })
+}
+
+/// Parse the headings and list items of a Markdown document into a tree
+///
+/// Headings contain all following headings of a higher level and list items.
+/// Nested list items are children of the list item before them.
+/// All other lines like paragraphs or code blocks are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::parse_markdown;
+/// let markdown = "# Fruits\n- Apple\n  - Boskoop\n- Pear\n# Vegetables\n";
+/// let items = parse_markdown(markdown).unwrap();
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items[0].children().len(), 2);
+/// ```
+///
+/// # Errors
+///
+/// Errors when a nested list item does not align with the list items before.
+pub fn parse_markdown(text: &str) -> Result<Vec<TreeItem<'_>>, OutlineError> {
+    /// Headings are always above list items, lower levels and indentations are above the higher ones
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    enum Rank {
+        Heading(usize),
+        ListItem(usize),
+    }
+
+    let mut nodes = Vec::new();
+    let mut open = Vec::<Rank>::new();
+    let mut in_code_block = false;
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        let indentation = line[..line.len() - trimmed.len()]
+            .chars()
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        let (rank, content) = if let Some((level, content)) = parse_heading(trimmed) {
+            (Rank::Heading(level), content)
+        } else if let Some(content) = parse_list_item(trimmed) {
+            (Rank::ListItem(indentation), content)
+        } else {
+            continue;
+        };
+
+        let mut closed = None;
+        while let Some(last) = open.last() {
+            if *last < rank {
+                break;
+            }
+            closed = open.pop();
+        }
+        if let (Some(Rank::ListItem(closed)), Some(Rank::ListItem(_)), Rank::ListItem(current)) =
+            (closed, open.last(), rank)
+        {
+            if closed > current {
+                return Err(OutlineError {
+                    line: index + 1,
+                    kind: OutlineErrorKind::UnalignedListItem {
+                        indentation: current,
+                    },
+                });
+            }
+        }
+
+        nodes.push((open.len(), content));
+        open.push(rank);
+    }
+    Ok(build(nodes))
+}
+
+/// Level and content of an ATX heading like `## Heading`
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') && !rest.starts_with('\t') {
+        return None;
+    }
+    let content = rest.trim().trim_end_matches('#').trim_end();
+    Some((level, content))
+}
+
+/// Content of a list item like `- item` or `1. item`
+fn parse_list_item(line: &str) -> Option<&str> {
+    let rest = if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        rest
+    } else {
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        let rest = &line[digits..];
+        rest.strip_prefix(". ")
+            .or_else(|| rest.strip_prefix(") "))?
+    };
+    Some(rest.trim())
+}
+
+/// Build the items from their depth and text in order.
+/// Each depth is at most one deeper than the one before.
+fn build<'a>(nodes: Vec<(usize, &'a str)>) -> Vec<TreeItem<'a>> {
+    let mut result = Vec::new();
+    let mut open = Vec::<TreeItem>::new();
+    for (depth, content) in nodes {
+        while open.len() > depth {
+            close_last(&mut open, &mut result);
+        }
+        open.push(TreeItem::new_leaf(content));
+    }
+    while !open.is_empty() {
+        close_last(&mut open, &mut result);
+    }
+    result
+}
+
+fn close_last<'a>(open: &mut Vec<TreeItem<'a>>, result: &mut Vec<TreeItem<'a>>) {
+    if let Some(item) = open.pop() {
+        match open.last_mut() {
+            Some(parent) => parent.add_child(item),
+            None => result.push(item),
+        }
+    }
+}
+
+#[test]
+fn indented_spaces() {
+    let items = parse_indented("a\n  b\n    c\n\n  d\ne\n", Indentation::Spaces(2)).unwrap();
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        ["a", " b", "  c", " d", "e"]
+    );
+}
+
+#[test]
+fn indented_detect_tabs() {
+    let items = parse_indented("a\n\tb\n\t\tc\nd", Indentation::Detect).unwrap();
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        ["a", " b", "  c", "d"]
+    );
+}
+
+#[test]
+fn indented_errors() {
+    let error = parse_indented("a\n   b", Indentation::Spaces(2)).unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(
+        error.kind,
+        OutlineErrorKind::UnevenIndentation {
+            spaces: 3,
+            per_level: 2
+        }
+    );
+
+    let error = parse_indented("a\n\tb\n\t c", Indentation::Tabs).unwrap_err();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.kind, OutlineErrorKind::MixedIndentation);
+
+    let error = parse_indented("\n  a", Indentation::Detect).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 2: indentation depth 1 is deeper than the allowed 0"
+    );
+
+    let error = parse_indented("a\n  b\n      c", Indentation::Detect).unwrap_err();
+    assert_eq!(
+        error.kind,
+        OutlineErrorKind::SkippedLevel {
+            depth: 3,
+            max_depth: 2
+        }
+    );
+}
+
+#[test]
+fn tree_command() {
+    let text = ".\n\u{251c}\u{2500}\u{2500} src\n\u{2502}\u{a0}\u{a0} \u{2514}\u{2500}\u{2500} lib.rs\n\u{2514}\u{2500}\u{2500} Cargo.toml\n\n1 directory, 2 files\n";
+    let items = parse_indented(text, Indentation::TreeCommand).unwrap();
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        [".", " src", "  lib.rs", " Cargo.toml"]
+    );
+
+    let text = ".\n\u{2514}\u{2500}\u{2500} 3 files\n\n0 directories, 1 file\n";
+    let items = parse_indented(text, Indentation::TreeCommand).unwrap();
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        [".", " 3 files"]
+    );
+}
+
+#[test]
+fn markdown() {
+    let markdown = "
+# Title
+Some paragraph
+## Section ##
+- a
+  - b
+    1. c
+  - d
+```
+# not a heading
+```
+### Skipped level
+* e
+# Second
+";
+    let items = parse_markdown(markdown).unwrap();
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        [
+            "Title",
+            " Section",
+            "  a",
+            "   b",
+            "    c",
+            "   d",
+            "  Skipped level",
+            "   e",
+            "Second"
+        ]
+    );
+}
+
+#[test]
+fn markdown_unaligned_list_item() {
+    let error = parse_markdown("- a\n    - b\n  - c").unwrap_err();
+    assert_eq!(error.line, 3);
+    assert_eq!(
+        error.kind,
+        OutlineErrorKind::UnalignedListItem { indentation: 2 }
+    );
+}
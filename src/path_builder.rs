@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::TreeItem;
+
+/// Builds [`TreeItem`s](TreeItem) from flat paths like `src/lib.rs`
+///
+/// Paths sharing a prefix are merged into the same parent node.
+/// Empty segments (as in `a//b` or `/a`) are skipped.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::PathTreeBuilder;
+/// let items = PathTreeBuilder::new()
+///     .sorted(true)
+///     .build(["src/lib.rs", "Cargo.toml", "src/flatten.rs"]);
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items[1].children().len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathTreeBuilder {
+    separator: char,
+    sorted: bool,
+}
+
+impl Default for PathTreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+struct Node<P> {
+    name: String,
+    payload: Option<P>,
+    children: Vec<Node<P>>,
+    /// Index into `children` by their name
+    child_index: HashMap<String, usize>,
+}
+
+impl<P> Node<P> {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            payload: None,
+            children: Vec::new(),
+            child_index: HashMap::new(),
+        }
+    }
+
+    /// Get the child with the `name` or append a new one
+    fn child_mut(&mut self, name: &str) -> &mut Self {
+        let index = if let Some(index) = self.child_index.get(name) {
+            *index
+        } else {
+            self.children.push(Self::new(name.to_string()));
+            let index = self.children.len() - 1;
+            self.child_index.insert(name.to_string(), index);
+            index
+        };
+        &mut self.children[index]
+    }
+
+    /// Sort the children recursively.
+    /// Only done after all paths were added as the `child_index` is dropped.
+    fn sort(&mut self) {
+        self.children.sort_by(|a, b| a.name.cmp(&b.name));
+        self.child_index = HashMap::new();
+        for child in &mut self.children {
+            child.sort();
+        }
+    }
+
+    fn into_item<'a>(
+        self,
+        identifier: TreeIdentifierVec,
+        payloads: &mut HashMap<TreeIdentifierVec, P>,
+    ) -> TreeItem<'a> {
+        let children = into_items(self.children, &identifier, payloads);
+        if let Some(payload) = self.payload {
+            payloads.insert(identifier, payload);
+        }
+        TreeItem::new(self.name, children)
+    }
+}
+
+fn into_items<'a, P>(
+    nodes: Vec<Node<P>>,
+    parent: &[usize],
+    payloads: &mut HashMap<TreeIdentifierVec, P>,
+) -> Vec<TreeItem<'a>> {
+    nodes
+        .into_iter()
+        .enumerate()
+        .map(|(index, node)| {
+            let mut identifier = parent.to_vec();
+            identifier.push(index);
+            node.into_item(identifier, payloads)
+        })
+        .collect()
+}
+
+impl PathTreeBuilder {
+    /// Create a builder splitting on `/` and keeping the order the paths are given in
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            separator: '/',
+            sorted: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sort siblings by name instead of keeping the order of their first occurrence
+    #[must_use]
+    pub const fn sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Build the items from the given `paths`
+    #[must_use]
+    pub fn build<'a, I, S>(self, paths: I) -> Vec<TreeItem<'a>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let (items, _) = self.build_with_payloads(paths.into_iter().map(|path| (path, ())));
+        items
+    }
+
+    /// Build the items from the given paths and attach a payload to the node each path ends in
+    ///
+    /// The payloads are returned by the identifier of their node.
+    /// When the same path is given multiple times the last payload wins.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::PathTreeBuilder;
+    /// let (items, payloads) = PathTreeBuilder::new()
+    ///     .build_with_payloads([("src/lib.rs", 'M'), ("README.md", '?')]);
+    /// assert_eq!(items.len(), 2);
+    /// assert_eq!(payloads[&vec![0, 0]], 'M');
+    /// assert_eq!(payloads[&vec![1]], '?');
+    /// ```
+    #[must_use]
+    pub fn build_with_payloads<'a, I, S, P>(
+        self,
+        paths: I,
+    ) -> (Vec<TreeItem<'a>>, HashMap<TreeIdentifierVec, P>)
+    where
+        I: IntoIterator<Item = (S, P)>,
+        S: AsRef<str>,
+    {
+        let mut root = Node::new(String::new());
+        for (path, payload) in paths {
+            let mut current = &mut root;
+            let mut is_empty = true;
+            for segment in path
+                .as_ref()
+                .split(self.separator)
+                .filter(|s| !s.is_empty())
+            {
+                current = current.child_mut(segment);
+                is_empty = false;
+            }
+            if !is_empty {
+                current.payload = Some(payload);
+            }
+        }
+
+        if self.sorted {
+            root.sort();
+        }
+
+        let mut payloads = HashMap::new();
+        let items = into_items(root.children, &[], &mut payloads);
+        (items, payloads)
+    }
+}
+
+#[test]
+fn merges_shared_prefixes() {
+    let items = PathTreeBuilder::new().build(["src/lib.rs", "README.md", "src/flatten.rs"]);
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        ["src", " lib.rs", " flatten.rs", "README.md"]
+    );
+}
+
+#[test]
+fn sorted_and_custom_separator() {
+    let items = PathTreeBuilder::new()
+        .separator('.')
+        .sorted(true)
+        .build(["b.z", "a", "b.y.x", "b.y"]);
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        ["a", "b", " y", "  x", " z"]
+    );
+}
+
+#[test]
+fn skips_empty_segments() {
+    let items = PathTreeBuilder::new().build(["/a//b/", "", "a/c"]);
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        ["a", " b", " c"]
+    );
+}
+
+#[test]
+fn payloads_follow_sorting() {
+    let (items, payloads) = PathTreeBuilder::new().sorted(true).build_with_payloads([
+        ("z", 1),
+        ("a/b", 2),
+        ("a", 3),
+        ("z", 4),
+    ]);
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        ["a", " b", "z"]
+    );
+    assert_eq!(payloads.len(), 3);
+    assert_eq!(payloads[&vec![0]], 3);
+    assert_eq!(payloads[&vec![0, 0]], 2);
+    assert_eq!(payloads[&vec![1]], 4);
+}
@@ -0,0 +1,174 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Single line text input used to edit the text of a [`TreeItem`](crate::TreeItem) in place
+///
+/// The cursor is a position between characters, `0` being in front of the first one.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::TextInput;
+/// let mut input = TextInput::new("ab");
+/// input.move_cursor_home();
+/// input.insert_char('x');
+/// assert_eq!(input.value(), "xab");
+/// assert_eq!(input.cursor(), 1);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextInput {
+    value: String,
+    /// Cursor position in characters
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Create an input containing `value` with the cursor at its end
+    #[must_use]
+    pub fn new<T>(value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn into_value(self) -> String {
+        self.value
+    }
+
+    #[must_use]
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.value.len(), |(index, _)| index)
+    }
+
+    /// Insert a character in front of the cursor.
+    /// Line breaks are ignored as the input is single line.
+    pub fn insert_char(&mut self, c: char) {
+        if c == '\n' || c == '\r' {
+            return;
+        }
+        let index = self.byte_index(self.cursor);
+        self.value.insert(index, c);
+        self.cursor += 1;
+    }
+
+    /// Delete the character in front of the cursor (Backspace).
+    /// Returns `false` when the cursor is at the start.
+    pub fn delete_before_cursor(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        let index = self.byte_index(self.cursor);
+        self.value.remove(index);
+        true
+    }
+
+    /// Delete the character after the cursor (Delete).
+    /// Returns `false` when the cursor is at the end.
+    pub fn delete_at_cursor(&mut self) -> bool {
+        let index = self.byte_index(self.cursor);
+        if index >= self.value.len() {
+            return false;
+        }
+        self.value.remove(index);
+        true
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.cursor = self
+            .cursor
+            .saturating_add(1)
+            .min(self.value.chars().count());
+    }
+
+    pub fn move_cursor_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.cursor = self.value.chars().count();
+    }
+
+    /// Part of the value to show within `width` columns keeping the cursor visible.
+    /// Returns the visible text and the column of the cursor inside of it.
+    pub(crate) fn visible(&self, width: usize) -> (String, usize) {
+        let chars = self.value.chars().collect::<Vec<_>>();
+        let char_width = |c: &char| c.width().unwrap_or(0);
+
+        // Keep one column for the cursor when it is at the end
+        let mut start = 0;
+        let mut cursor_column = chars[..self.cursor].iter().map(char_width).sum::<usize>();
+        while start < self.cursor && cursor_column >= width {
+            cursor_column -= char_width(&chars[start]);
+            start += 1;
+        }
+
+        let mut used = 0;
+        let visible = chars[start..]
+            .iter()
+            .take_while(|c| {
+                used += char_width(c);
+                used <= width
+            })
+            .collect();
+        (visible, cursor_column)
+    }
+}
+
+#[test]
+fn insert_and_delete() {
+    let mut input = TextInput::new("hello");
+    assert_eq!(input.cursor(), 5);
+    input.move_cursor_left();
+    input.move_cursor_left();
+    input.insert_char('X');
+    assert_eq!(input.value(), "helXlo");
+    assert!(input.delete_before_cursor());
+    assert!(input.delete_at_cursor());
+    assert_eq!(input.value(), "helo");
+    input.move_cursor_end();
+    assert!(!input.delete_at_cursor());
+    input.move_cursor_home();
+    assert!(!input.delete_before_cursor());
+    input.insert_char('\n');
+    assert_eq!(input.value(), "helo");
+}
+
+#[test]
+fn multibyte_characters() {
+    let mut input = TextInput::new("äöü");
+    input.move_cursor_left();
+    input.delete_before_cursor();
+    assert_eq!(input.value(), "äü");
+    input.move_cursor_right();
+    input.move_cursor_right();
+    assert_eq!(input.cursor(), 2);
+}
+
+#[test]
+fn visible_scrolls_to_cursor() {
+    let mut input = TextInput::new("abcdef");
+    assert_eq!(input.visible(4), ("def".to_string(), 3));
+    input.move_cursor_home();
+    assert_eq!(input.visible(4), ("abcd".to_string(), 0));
+}
@@ -0,0 +1,265 @@
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+
+use crate::TreeItem;
+
+/// Converts structured values like `serde_json::Value` or `toml::Value` into [`TreeItem`s](TreeItem)
+///
+/// Objects and arrays become parents labelled with their key or index and the amount of children.
+/// Scalars become leaves showing `key: value` with a style depending on their type.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "serde_json")] {
+/// # use tui_tree_widget::ValueTreeBuilder;
+/// let value = serde_json::json!({ "name": "tree", "tags": ["tui", "widget"] });
+/// let items = ValueTreeBuilder::new().build_json(&value);
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items[1].children().len(), 2);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_field_names)]
+pub struct ValueTreeBuilder {
+    key_style: Style,
+    /// Style of the type info like `{3}` or `[2]` behind the key of objects and arrays
+    type_style: Style,
+    string_style: Style,
+    number_style: Style,
+    bool_style: Style,
+    null_style: Style,
+    datetime_style: Style,
+}
+
+impl Default for ValueTreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValueTreeBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            key_style: Style::default(),
+            type_style: Style::default().fg(Color::DarkGray),
+            string_style: Style::default().fg(Color::Green),
+            number_style: Style::default().fg(Color::Cyan),
+            bool_style: Style::default().fg(Color::Yellow),
+            null_style: Style::default().fg(Color::DarkGray),
+            datetime_style: Style::default().fg(Color::Magenta),
+        }
+    }
+
+    #[must_use]
+    pub const fn key_style(mut self, style: Style) -> Self {
+        self.key_style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn type_style(mut self, style: Style) -> Self {
+        self.type_style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn string_style(mut self, style: Style) -> Self {
+        self.string_style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn number_style(mut self, style: Style) -> Self {
+        self.number_style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn bool_style(mut self, style: Style) -> Self {
+        self.bool_style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn null_style(mut self, style: Style) -> Self {
+        self.null_style = style;
+        self
+    }
+
+    /// Style of TOML datetimes
+    #[must_use]
+    pub const fn datetime_style(mut self, style: Style) -> Self {
+        self.datetime_style = style;
+        self
+    }
+
+    fn parent(
+        self,
+        key: String,
+        type_info: String,
+        children: Vec<TreeItem<'static>>,
+    ) -> TreeItem<'static> {
+        let text = Spans::from(vec![
+            Span::styled(key, self.key_style),
+            Span::raw(" "),
+            Span::styled(type_info, self.type_style),
+        ]);
+        TreeItem::new(text, children)
+    }
+
+    fn leaf(self, key: Option<String>, value: String, style: Style) -> TreeItem<'static> {
+        let mut spans = Vec::new();
+        if let Some(key) = key {
+            spans.push(Span::styled(key, self.key_style));
+            spans.push(Span::raw(": "));
+        }
+        spans.push(Span::styled(value, style));
+        TreeItem::new_leaf(Spans::from(spans))
+    }
+
+    /// Convert a JSON value.
+    /// The entries of a top level object or array become the top level items.
+    #[cfg(feature = "serde_json")]
+    #[must_use]
+    pub fn build_json(self, value: &serde_json::Value) -> Vec<TreeItem<'static>> {
+        use serde_json::Value;
+        match value {
+            Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| self.json_item(Some(key.clone()), value))
+                .collect(),
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| self.json_item(Some(index.to_string()), value))
+                .collect(),
+            _ => vec![self.json_item(None, value)],
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    fn json_item(self, key: Option<String>, value: &serde_json::Value) -> TreeItem<'static> {
+        use serde_json::Value;
+        match value {
+            Value::Object(map) => self.parent(
+                key.unwrap_or_default(),
+                format!("{{{}}}", map.len()),
+                self.build_json(value),
+            ),
+            Value::Array(array) => self.parent(
+                key.unwrap_or_default(),
+                format!("[{}]", array.len()),
+                self.build_json(value),
+            ),
+            Value::String(string) => self.leaf(key, format!("{:?}", string), self.string_style),
+            Value::Number(number) => self.leaf(key, number.to_string(), self.number_style),
+            Value::Bool(bool) => self.leaf(key, bool.to_string(), self.bool_style),
+            Value::Null => self.leaf(key, "null".to_string(), self.null_style),
+        }
+    }
+
+    /// Convert a TOML value.
+    /// The entries of a top level table or array become the top level items.
+    #[cfg(feature = "toml")]
+    #[must_use]
+    pub fn build_toml(self, value: &toml::Value) -> Vec<TreeItem<'static>> {
+        use toml::Value;
+        match value {
+            Value::Table(table) => table
+                .iter()
+                .map(|(key, value)| self.toml_item(Some(key.clone()), value))
+                .collect(),
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| self.toml_item(Some(index.to_string()), value))
+                .collect(),
+            _ => vec![self.toml_item(None, value)],
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    fn toml_item(self, key: Option<String>, value: &toml::Value) -> TreeItem<'static> {
+        use toml::Value;
+        match value {
+            Value::Table(table) => self.parent(
+                key.unwrap_or_default(),
+                format!("{{{}}}", table.len()),
+                self.build_toml(value),
+            ),
+            Value::Array(array) => self.parent(
+                key.unwrap_or_default(),
+                format!("[{}]", array.len()),
+                self.build_toml(value),
+            ),
+            Value::String(string) => self.leaf(key, format!("{:?}", string), self.string_style),
+            Value::Integer(integer) => self.leaf(key, integer.to_string(), self.number_style),
+            Value::Float(float) => self.leaf(key, float.to_string(), self.number_style),
+            Value::Boolean(bool) => self.leaf(key, bool.to_string(), self.bool_style),
+            Value::Datetime(datetime) => self.leaf(key, datetime.to_string(), self.datetime_style),
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json() {
+    let value = serde_json::json!({
+        "a": [1, "two", null],
+        "b": { "c": true },
+    });
+    let items = ValueTreeBuilder::new().build_json(&value);
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        [
+            "a [3]",
+            " 0: 1",
+            " 1: \"two\"",
+            " 2: null",
+            "b {1}",
+            " c: true"
+        ]
+    );
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json_scalar_without_key() {
+    let items = ValueTreeBuilder::new().build_json(&serde_json::json!(4.5));
+    assert_eq!(crate::flatten::get_all_texts_indented(&items), ["4.5"]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json_value_style() {
+    let builder = ValueTreeBuilder::new().number_style(Style::default().fg(Color::Red));
+    let items = builder.build_json(&serde_json::json!({ "a": 1 }));
+    let spans = &items[0].text.lines[0].0;
+    assert_eq!(spans[2].style, Style::default().fg(Color::Red));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml() {
+    let value = r#"
+        name = "tree"
+        [package]
+        version = 1.5
+        keywords = ["tui"]
+    "#
+    .parse::<toml::Value>()
+    .unwrap();
+    let items = ValueTreeBuilder::new().build_toml(&value);
+    assert_eq!(
+        crate::flatten::get_all_texts_indented(&items),
+        [
+            "name: \"tree\"",
+            "package {2}",
+            " keywords [1]",
+            "  0: \"tui\"",
+            " version: 1.5"
+        ]
+    );
+}
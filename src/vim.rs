@@ -0,0 +1,235 @@
+use crate::{Key, NavigationOutcome, TreeAction, TreeItem, TreeState};
+
+/// Result of passing a key to [`VimKeymap::handle_key`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimOutcome {
+    /// The key started or continued a sequence like `5` or `z` which needs more keys
+    Pending,
+    /// The sequence is complete and the action ran as often as its count asked for
+    Ran(TreeAction),
+    /// The pending sequence was aborted by `Esc` or a key which does not continue it
+    Cancelled,
+    /// The key is not used by the keymap
+    Unhandled,
+}
+
+/// Vim style key bindings with count prefixes for [`TreeState`]
+///
+/// | Keys | Action |
+/// |------|--------|
+/// | `j` `k` `h` `l` | [`KeyDown`](TreeAction::KeyDown), [`KeyUp`](TreeAction::KeyUp), [`KeyLeft`](TreeAction::KeyLeft), [`KeyRight`](TreeAction::KeyRight) |
+/// | `gg` `G` | [`SelectFirst`](TreeAction::SelectFirst), [`SelectLast`](TreeAction::SelectLast) |
+/// | `zo` `zc` `za` | [`OpenSelected`](TreeAction::OpenSelected), [`CloseSelected`](TreeAction::CloseSelected), [`ToggleSelected`](TreeAction::ToggleSelected) |
+/// | `zR` `zM` | [`OpenAll`](TreeAction::OpenAll), [`CloseAll`](TreeAction::CloseAll) |
+/// | `[` `]` | [`SelectParent`](TreeAction::SelectParent), [`SelectNextSibling`](TreeAction::SelectNextSibling) |
+/// | `Ctrl-d` `Ctrl-u` | [`HalfPageDown`](TreeAction::HalfPageDown), [`HalfPageUp`](TreeAction::HalfPageUp) |
+/// | `Ctrl-f` `Ctrl-b` | [`PageDown`](TreeAction::PageDown), [`PageUp`](TreeAction::PageUp) |
+/// | `Ctrl-e` `Ctrl-y` | [`ScrollDown`](TreeAction::ScrollDown), [`ScrollUp`](TreeAction::ScrollUp) |
+/// | `m{name}` `'{name}` | [`SetMark`](TreeAction::SetMark), [`JumpToMark`](TreeAction::JumpToMark) |
+/// | `Ctrl-o` `Tab` | [`JumpBack`](TreeAction::JumpBack), [`JumpForward`](TreeAction::JumpForward) |
+///
+/// A count like `5j` repeats the action until it does not move the selection anymore.
+/// As the keymap remembers pending keys one instance should be kept per tree.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{Key, TreeAction, TreeItem, TreeState, VimKeymap, VimOutcome};
+/// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b"), TreeItem::new_leaf("c")];
+/// let mut state = TreeState::default();
+/// let mut vim = VimKeymap::new();
+///
+/// assert_eq!(vim.handle_key(&mut state, &items, Key::Char('3')), VimOutcome::Pending);
+/// assert_eq!(
+///     vim.handle_key(&mut state, &items, Key::Char('j')),
+///     VimOutcome::Ran(TreeAction::KeyDown)
+/// );
+/// assert_eq!(state.selected(), [2]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VimKeymap {
+    /// Typed count, `0` when none was typed
+    count: usize,
+    /// First key of a two key sequence like `g`, `z` or `m`
+    prefix: Option<char>,
+}
+
+impl VimKeymap {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            prefix: None,
+        }
+    }
+
+    /// Returns `true` when keys of an incomplete sequence were typed
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        self.count > 0 || self.prefix.is_some()
+    }
+
+    /// The keys of the incomplete sequence like `5z` for displaying them
+    #[must_use]
+    pub fn pending_keys(&self) -> String {
+        let mut keys = String::new();
+        if self.count > 0 {
+            keys += &self.count.to_string();
+        }
+        keys.extend(self.prefix);
+        keys
+    }
+
+    /// Forget the pending keys.
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.prefix = None;
+    }
+
+    /// Handle the `key` and run the action of a completed sequence.
+    pub fn handle_key(
+        &mut self,
+        state: &mut TreeState,
+        items: &[TreeItem],
+        key: Key,
+    ) -> VimOutcome {
+        let was_pending = self.is_pending();
+        let action = match (self.prefix, key) {
+            (None, Key::Char(digit @ '0'..='9')) if digit != '0' || self.count > 0 => {
+                let digit = digit.to_digit(10).unwrap_or_default() as usize;
+                self.count = self.count.saturating_mul(10).saturating_add(digit);
+                return VimOutcome::Pending;
+            }
+            (None, Key::Char(prefix @ ('g' | 'z' | 'm' | '\''))) => {
+                self.prefix = Some(prefix);
+                return VimOutcome::Pending;
+            }
+            (Some('g'), Key::Char('g')) => Some(TreeAction::SelectFirst),
+            (Some('z'), Key::Char('o')) => Some(TreeAction::OpenSelected),
+            (Some('z'), Key::Char('c')) => Some(TreeAction::CloseSelected),
+            (Some('z'), Key::Char('a')) => Some(TreeAction::ToggleSelected),
+            (Some('z'), Key::Char('R')) => Some(TreeAction::OpenAll),
+            (Some('z'), Key::Char('M')) => Some(TreeAction::CloseAll),
+            (Some('m'), Key::Char(name)) => Some(TreeAction::SetMark(name)),
+            (Some('\''), Key::Char(name)) => Some(TreeAction::JumpToMark(name)),
+            (Some(_), _) => None,
+            (None, key) => get_single_key_action(key),
+        };
+        let count = self.count.max(1);
+        self.reset();
+
+        match action {
+            Some(action) => {
+                for _ in 0..count {
+                    if state.apply_action(items, action) != NavigationOutcome::Moved {
+                        break;
+                    }
+                }
+                VimOutcome::Ran(action)
+            }
+            None if was_pending => VimOutcome::Cancelled,
+            None => VimOutcome::Unhandled,
+        }
+    }
+}
+
+const fn get_single_key_action(key: Key) -> Option<TreeAction> {
+    let action = match key {
+        Key::Char('j') => TreeAction::KeyDown,
+        Key::Char('k') => TreeAction::KeyUp,
+        Key::Char('h') => TreeAction::KeyLeft,
+        Key::Char('l') => TreeAction::KeyRight,
+        Key::Char('G') => TreeAction::SelectLast,
+        Key::Char('[') => TreeAction::SelectParent,
+        Key::Char(']') => TreeAction::SelectNextSibling,
+        Key::Ctrl('d') => TreeAction::HalfPageDown,
+        Key::Ctrl('u') => TreeAction::HalfPageUp,
+        Key::Ctrl('f') => TreeAction::PageDown,
+        Key::Ctrl('b') => TreeAction::PageUp,
+        Key::Ctrl('e') => TreeAction::ScrollDown,
+        Key::Ctrl('y') => TreeAction::ScrollUp,
+        Key::Ctrl('o') => TreeAction::JumpBack,
+        Key::Tab | Key::Ctrl('i') => TreeAction::JumpForward,
+        _ => return None,
+    };
+    Some(action)
+}
+
+#[cfg(test)]
+fn type_keys(vim: &mut VimKeymap, state: &mut TreeState, items: &[TreeItem], keys: &str) {
+    for c in keys.chars() {
+        vim.handle_key(state, items, Key::Char(c));
+    }
+}
+
+#[test]
+fn count_prefix() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let mut vim = VimKeymap::new();
+    type_keys(&mut vim, &mut state, &items, "zRj1");
+    assert_eq!(vim.pending_keys(), "1");
+    type_keys(&mut vim, &mut state, &items, "0j");
+    assert!(!vim.is_pending());
+    assert_eq!(state.selected(), [2]);
+    type_keys(&mut vim, &mut state, &items, "3k");
+    assert_eq!(state.selected(), [1, 1, 0]);
+    type_keys(&mut vim, &mut state, &items, "gg");
+    assert_eq!(state.selected(), [0]);
+}
+
+#[test]
+fn fold_sequences() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let mut vim = VimKeymap::new();
+    type_keys(&mut vim, &mut state, &items, "jjzo");
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+    type_keys(&mut vim, &mut state, &items, "j]");
+    assert_eq!(state.selected(), [1, 1]);
+    type_keys(&mut vim, &mut state, &items, "zazR[");
+    assert_eq!(state.selected(), [1]);
+    assert_eq!(state.get_all_opened().len(), 2);
+    type_keys(&mut vim, &mut state, &items, "zM");
+    assert!(state.get_all_opened().is_empty());
+}
+
+#[test]
+fn marks() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let mut vim = VimKeymap::new();
+    type_keys(&mut vim, &mut state, &items, "Gmxgg");
+    assert_eq!(state.mark('x'), Some(&[2][..]));
+    assert_eq!(
+        vim.handle_key(&mut state, &items, Key::Char('\'')),
+        VimOutcome::Pending
+    );
+    assert_eq!(
+        vim.handle_key(&mut state, &items, Key::Char('x')),
+        VimOutcome::Ran(TreeAction::JumpToMark('x'))
+    );
+    assert_eq!(state.selected(), [2]);
+}
+
+#[test]
+fn cancel_pending() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let mut vim = VimKeymap::new();
+    assert_eq!(
+        vim.handle_key(&mut state, &items, Key::Char('0')),
+        VimOutcome::Unhandled
+    );
+    type_keys(&mut vim, &mut state, &items, "5z");
+    assert_eq!(vim.pending_keys(), "5z");
+    assert_eq!(
+        vim.handle_key(&mut state, &items, Key::Esc),
+        VimOutcome::Cancelled
+    );
+    assert!(!vim.is_pending());
+    assert_eq!(
+        vim.handle_key(&mut state, &items, Key::Char('x')),
+        VimOutcome::Unhandled
+    );
+}